@@ -12,6 +12,16 @@
 // Local imports.
 use canvas::Canvas;
 use geometry::Point;
+use geometry::Position;
+use pattern::blend_channels;
+use pattern::Pattern;
+use primitive;
+use utilities::clamped;
+use utilities::lerp;
+
+// Standard library imports.
+use std::cell::RefCell;
+use std::collections::HashSet;
 
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -23,8 +33,33 @@ pub trait Brush<X> {
     fn apply<C>(&self, canvas: &mut C, pt: Point)
     	where C: Canvas<Pixel=X>;
 
+    /// Applies a single stamp of the brush to the given canvas, with its
+    /// footprint scaled by `size` and its effect scaled by `opacity`.
+    ///
+    /// Brushes which cannot vary their footprint or opacity may ignore these
+    /// parameters, which is the default behavior.
+    #[inline]
+    fn apply_stamp<C>(&self, canvas: &mut C, pt: Point, size: f32, opacity: f32)
+        where C: Canvas<Pixel=X>
+    {
+        let _ = (size, opacity);
+        self.apply(canvas, pt);
+    }
+
+    /// Applies the brush along the polyline connecting the given vertices,
+    /// using the default [`StrokeStyle`].
+    ///
+    /// [`StrokeStyle`]: struct.StrokeStyle.html
+    #[inline]
     fn stroke<C>(&self, canvas: &mut C, vertices: &[Point])
-        where C: Canvas<Pixel=X>;
+        where C: Canvas<Pixel=X>
+    {
+        primitive::stroke(
+            canvas,
+            self,
+            StrokeStyle::default(),
+            vertices.iter().cloned());
+    }
 
     /// Returns the size of the brush.
     #[inline]
@@ -34,6 +69,84 @@ pub trait Brush<X> {
 }
 
 
+////////////////////////////////////////////////////////////////////////////////
+// StrokeStyle
+////////////////////////////////////////////////////////////////////////////////
+/// Parameters controlling the placement of brush stamps along a stroke.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrokeStyle {
+    /// The distance between consecutive stamps, as a fraction of the brush
+    /// size.
+    pub spacing: f32,
+    /// The stamp size scale at zero and full pressure.
+    pub size: (f32, f32),
+    /// The stamp opacity at zero and full pressure.
+    pub opacity: (f32, f32),
+}
+
+impl StrokeStyle {
+    /// Returns a new `StrokeStyle` with the given spacing and a constant stamp
+    /// size and opacity.
+    #[inline]
+    pub fn new(spacing: f32) -> Self {
+        StrokeStyle { spacing, .. Default::default() }
+    }
+
+    /// Returns the stamp size scale for the given pressure.
+    #[inline]
+    pub fn size_at(&self, pressure: f32) -> f32 {
+        lerp(self.size.0, self.size.1, pressure)
+    }
+
+    /// Returns the stamp opacity for the given pressure.
+    #[inline]
+    pub fn opacity_at(&self, pressure: f32) -> f32 {
+        lerp(self.opacity.0, self.opacity.1, pressure)
+    }
+}
+
+impl Default for StrokeStyle {
+    #[inline]
+    fn default() -> Self {
+        StrokeStyle {
+            spacing: 0.25,
+            size: (1.0, 1.0),
+            opacity: (1.0, 1.0),
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// StrokePoint
+////////////////////////////////////////////////////////////////////////////////
+/// A stroke vertex with an associated pen pressure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrokePoint {
+    /// The location of the vertex.
+    pub pt: Point,
+    /// The pen pressure at the vertex, in the range `[0.0, 1.0]`.
+    pub pressure: f32,
+}
+
+impl StrokePoint {
+    /// Returns a new `StrokePoint` at the given `Point` with the given
+    /// pressure.
+    #[inline]
+    pub fn new(pt: Point, pressure: f32) -> Self {
+        StrokePoint { pt, pressure }
+    }
+}
+
+// Conversion traits
+
+impl From<Point> for StrokePoint {
+    #[inline]
+    fn from(pt: Point) -> Self {
+        StrokePoint { pt, pressure: 1.0 }
+    }
+}
+
 
 // Basic brushes.
 impl Brush<u32> for () {
@@ -59,12 +172,530 @@ impl Brush<u32> for u32 {
     {
     	canvas.aligned_pixel_mut(pt).map(|p| *p = *self);
     }
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// SquareBrush
+////////////////////////////////////////////////////////////////////////////////
+/// A brush which applies another brush over a square footprint centered on
+/// each drawn point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SquareBrush<B> {
+    brush: B,
+    size: u32,
+}
+
+impl<B> SquareBrush<B> {
+    /// Returns a new `SquareBrush` applying the given brush over a square with
+    /// sides of the given size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given size is zero.
+    #[inline]
+    pub fn new(brush: B, size: u32) -> Self {
+        assert!(size > 0, "brush size must be nonzero");
+        SquareBrush { brush, size }
+    }
+}
 
+impl<B, X> Brush<X> for SquareBrush<B> where B: Brush<X> {
     #[inline]
-    fn stroke<C>(&self, _canvas: &mut C, _vertices: &[Point])
+    fn apply<C>(&self, canvas: &mut C, pt: Point)
+        where C: Canvas<Pixel=X>
+    {
+        self.apply_stamp(canvas, pt, 1.0, 1.0);
+    }
+
+    fn apply_stamp<C>(&self, canvas: &mut C, pt: Point, size: f32, opacity: f32)
+        where C: Canvas<Pixel=X>
+    {
+        let side = ((self.size as f32 * size).round() as u32).max(1);
+        let offset = (side / 2) as f32;
+        for j in 0..side {
+            for i in 0..side {
+                self.brush.apply_stamp(canvas, Point {
+                    x: pt.x + i as f32 - offset,
+                    y: pt.y + j as f32 - offset,
+                }, 1.0, opacity);
+            }
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> (u32, u32) {
+        (self.size, self.size)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// RoundBrush
+////////////////////////////////////////////////////////////////////////////////
+/// A brush which applies another brush over a circular footprint centered on
+/// each drawn point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoundBrush<B> {
+    brush: B,
+    radius: f32,
+}
+
+impl<B> RoundBrush<B> {
+    /// Returns a new `RoundBrush` applying the given brush over a circle with
+    /// the given radius. A radius of zero applies the brush to a single pixel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given radius is negative or not finite.
+    #[inline]
+    pub fn new(brush: B, radius: f32) -> Self {
+        assert!(radius >= 0.0 && radius.is_finite(), "invalid brush radius");
+        RoundBrush { brush, radius }
+    }
+
+    /// Returns the number of whole pixels the footprint extends from its
+    /// center.
+    #[inline]
+    fn extent(&self) -> i32 {
+        self.radius.floor() as i32
+    }
+}
+
+impl<B, X> Brush<X> for RoundBrush<B> where B: Brush<X> {
+    #[inline]
+    fn apply<C>(&self, canvas: &mut C, pt: Point)
+        where C: Canvas<Pixel=X>
+    {
+        self.apply_stamp(canvas, pt, 1.0, 1.0);
+    }
+
+    fn apply_stamp<C>(&self, canvas: &mut C, pt: Point, size: f32, opacity: f32)
+        where C: Canvas<Pixel=X>
+    {
+        let radius = (self.radius * size).max(0.0);
+        let extent = radius.floor() as i32;
+        let r_sq = radius * radius;
+        for j in -extent..(extent + 1) {
+            for i in -extent..(extent + 1) {
+                let (dx, dy) = (i as f32, j as f32);
+                if dx * dx + dy * dy <= r_sq {
+                    self.brush.apply_stamp(canvas, Point {
+                        x: pt.x + dx,
+                        y: pt.y + dy,
+                    }, 1.0, opacity);
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> (u32, u32) {
+        let diameter = 2 * self.extent() as u32 + 1;
+        (diameter, diameter)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// StampBrush
+////////////////////////////////////////////////////////////////////////////////
+/// A brush which applies another brush over an arbitrary bitmap footprint
+/// centered on each drawn point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StampBrush<B> {
+    brush: B,
+    mask: Vec<bool>,
+    width: u32,
+}
+
+impl<B> StampBrush<B> {
+    /// Returns a new `StampBrush` applying the given brush wherever the given
+    /// row-major bitmap mask is `true`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width is zero or the mask does not consist of a whole
+    /// number of rows of the given width.
+    #[inline]
+    pub fn new(brush: B, width: u32, mask: Vec<bool>) -> Self {
+        assert!(width > 0, "stamp width must be nonzero");
+        assert!(mask.len().is_multiple_of(width as usize),
+            "stamp mask must contain a whole number of rows");
+        StampBrush { brush, mask, width }
+    }
+
+    /// Returns the height of the stamp footprint.
+    #[inline]
+    fn height(&self) -> u32 {
+        (self.mask.len() / self.width as usize) as u32
+    }
+}
+
+impl<B, X> Brush<X> for StampBrush<B> where B: Brush<X> {
+    #[inline]
+    fn apply<C>(&self, canvas: &mut C, pt: Point)
+        where C: Canvas<Pixel=X>
+    {
+        self.apply_stamp(canvas, pt, 1.0, 1.0);
+    }
+
+    fn apply_stamp<C>(&self, canvas: &mut C, pt: Point, size: f32, opacity: f32)
+        where C: Canvas<Pixel=X>
+    {
+        let (w, h) = (self.width, self.height());
+        // Scale the footprint using nearest-neighbor sampling of the mask.
+        let sw = ((w as f32 * size).round() as u32).max(1);
+        let sh = ((h as f32 * size).round() as u32).max(1);
+        let x_offset = (sw / 2) as f32;
+        let y_offset = (sh / 2) as f32;
+        for j in 0..sh {
+            let row = (j * h / sh) as usize * w as usize;
+            for i in 0..sw {
+                if self.mask[row + (i * w / sw) as usize] {
+                    self.brush.apply_stamp(canvas, Point {
+                        x: pt.x + i as f32 - x_offset,
+                        y: pt.y + j as f32 - y_offset,
+                    }, 1.0, opacity);
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height())
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// SoftBrush
+////////////////////////////////////////////////////////////////////////////////
+/// A brush which applies a pattern over an anti-aliased circular footprint
+/// centered on each drawn point, with a feathered edge.
+///
+/// Each pixel's coverage of the disc is passed as the opacity to the pattern.
+/// The disc is centered half a pixel below and right of the drawn point, so
+/// that integral points produce the same footprint as a [`RoundBrush`].
+///
+/// [`RoundBrush`]: struct.RoundBrush.html
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoftBrush<P> {
+    pattern: P,
+    radius: f32,
+    hardness: f32,
+}
+
+impl<P> SoftBrush<P> {
+    /// Returns a new `SoftBrush` applying the given pattern over a disc with
+    /// the given radius and hardness.
+    ///
+    /// The hardness is the fraction of the radius which is fully covered. A
+    /// hardness of `1.0` gives a disc with an anti-aliased edge, while lower
+    /// values feather the coverage toward the edge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given radius is negative or not finite, or if the given
+    /// hardness is not in the range `[0.0, 1.0]`.
+    #[inline]
+    pub fn new(pattern: P, radius: f32, hardness: f32) -> Self {
+        assert!(radius >= 0.0 && radius.is_finite(), "invalid brush radius");
+        assert!((0.0..=1.0).contains(&hardness), "invalid brush hardness");
+        SoftBrush { pattern, radius, hardness }
+    }
+
+    /// Returns the coverage of a disc with the given radius at the given
+    /// distance from its center.
+    #[inline]
+    fn coverage(&self, radius: f32, dist: f32) -> f32 {
+        // Coverage falls off over a band at least one pixel wide, so that hard
+        // brushes are still anti-aliased.
+        let outer = radius + 0.5;
+        let inner = radius * self.hardness - 0.5;
+        let t = clamped((outer - dist) / (outer - inner), 0.0, 1.0);
+        // Smoothstep the falloff.
+        t * t * (3.0 - 2.0 * t)
+    }
+}
+
+impl<P, X> Brush<X> for SoftBrush<P> where P: Pattern<X> {
+    #[inline]
+    fn apply<C>(&self, canvas: &mut C, pt: Point)
+        where C: Canvas<Pixel=X>
+    {
+        self.apply_stamp(canvas, pt, 1.0, 1.0);
+    }
+
+    fn apply_stamp<C>(&self, canvas: &mut C, pt: Point, size: f32, opacity: f32)
+        where C: Canvas<Pixel=X>
+    {
+        let radius = (self.radius * size).max(0.0);
+        // Center the disc within the pixel cell of the drawn point.
+        let center = Point { x: pt.x + 0.5, y: pt.y + 0.5 };
+        let extent = radius + 0.5;
+
+        let mut y = (center.y - extent).floor();
+        while y < center.y + extent {
+            let mut x = (center.x - extent).floor();
+            while x < center.x + extent {
+                // Measure the distance to the pixel's center.
+                let dx = x + 0.5 - center.x;
+                let dy = y + 0.5 - center.y;
+                let coverage = self.coverage(radius, (dx * dx + dy * dy).sqrt());
+                if coverage > 0.0 {
+                    self.pattern.apply(canvas, Point { x, y }, coverage * opacity);
+                }
+                x += 1.0;
+            }
+            y += 1.0;
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> (u32, u32) {
+        let diameter = 2 * self.radius.ceil() as u32 + 1;
+        (diameter, diameter)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Read-modify-write brushes
+////////////////////////////////////////////////////////////////////////////////
+/// Replaces the pixel aligned to the given [`Point`] with the result of the
+/// given function applied to its current value. Does nothing if the `Point`
+/// lies outside the canvas boundary.
+///
+/// [`Point`]: geometry/struct.Point.html
+#[inline]
+fn modify_pixel<C, F>(canvas: &mut C, pt: Point, f: F)
+    where
+        C: Canvas<Pixel=u32>,
+        F: FnOnce(u32) -> u32
+{
+    if let Some(&old) = canvas.aligned_pixel(pt) {
+        if let Some(p) = canvas.aligned_pixel_mut(pt) {
+            *p = f(old);
+        }
+    }
+}
+
+/// Blends the color channels of two RGBA pixels, preserving the alpha channel
+/// of the background pixel.
+#[inline]
+fn blend_color(bg: u32, fg: u32, opacity: f32) -> u32 {
+    blend_channels(bg, fg, [opacity; 3], bg.to_bytes()[3])
+}
+
+/// Applies the given function to each pair of corresponding color channels of
+/// two RGBA pixels, preserving the alpha channel of the first pixel.
+#[inline]
+fn map_color<F>(a: u32, b: u32, f: F) -> u32 where F: Fn(u8, u8) -> u8 {
+    let a = a.to_bytes();
+    let b = b.to_bytes();
+    u32::from_bytes([f(a[0], b[0]), f(a[1], b[1]), f(a[2], b[2]), a[3]])
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// EraserBrush
+////////////////////////////////////////////////////////////////////////////////
+/// A brush which reduces the alpha channel of the pixels it is applied to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EraserBrush {
+    strength: f32,
+}
+
+impl EraserBrush {
+    /// Returns a new `EraserBrush` which removes the given fraction of each
+    /// pixel's alpha. A strength of `1.0` erases pixels completely.
+    #[inline]
+    pub fn new(strength: f32) -> Self {
+        EraserBrush { strength: clamped(strength, 0.0, 1.0) }
+    }
+}
+
+impl Default for EraserBrush {
+    #[inline]
+    fn default() -> Self {
+        EraserBrush { strength: 1.0 }
+    }
+}
+
+impl Brush<u32> for EraserBrush {
+    #[inline]
+    fn apply<C>(&self, canvas: &mut C, pt: Point)
+        where C: Canvas<Pixel=u32>
+    {
+        self.apply_stamp(canvas, pt, 1.0, 1.0);
+    }
+
+    #[inline]
+    fn apply_stamp<C>(&self, canvas: &mut C, pt: Point, _size: f32, opacity: f32)
         where C: Canvas<Pixel=u32>
     {
-        unimplemented!()
+        let keep = 1.0 - self.strength * clamped(opacity, 0.0, 1.0);
+        modify_pixel(canvas, pt, |p| {
+            let mut bytes = p.to_bytes();
+            bytes[3] = (bytes[3] as f32 * keep) as u8;
+            u32::from_bytes(bytes)
+        });
     }
 }
 
+
+////////////////////////////////////////////////////////////////////////////////
+// XorBrush
+////////////////////////////////////////////////////////////////////////////////
+/// A brush which XORs the pixels it is applied to with a fixed value.
+///
+/// Applying the brush twice to the same pixel restores its original value, so
+/// figures drawn with an `XorBrush` can be removed by redrawing them. Since
+/// this requires each pixel to be visited exactly once per figure, the brush
+/// ignores stamp opacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct XorBrush {
+    value: u32,
+}
+
+impl XorBrush {
+    /// Returns a new `XorBrush` which XORs pixels with the given value.
+    #[inline]
+    pub fn new(value: u32) -> Self {
+        XorBrush { value }
+    }
+
+    /// Returns a new `XorBrush` which inverts the color channels of pixels.
+    #[inline]
+    pub fn invert() -> Self {
+        XorBrush { value: u32::from_bytes([0xFF, 0xFF, 0xFF, 0x00]) }
+    }
+}
+
+impl Brush<u32> for XorBrush {
+    #[inline]
+    fn apply<C>(&self, canvas: &mut C, pt: Point)
+        where C: Canvas<Pixel=u32>
+    {
+        let value = self.value;
+        modify_pixel(canvas, pt, |p| p ^ value);
+    }
+
+    /// Applies the brush along the polyline connecting the given vertices.
+    ///
+    /// Consecutive stamps of a stroke overlap, so the pixels covered by the
+    /// stroke are collected first and then each is XORed exactly once.
+    fn stroke<C>(&self, canvas: &mut C, vertices: &[Point])
+        where C: Canvas<Pixel=u32>
+    {
+        let covered = CoverageBrush::default();
+        primitive::stroke(
+            canvas,
+            &covered,
+            StrokeStyle::default(),
+            vertices.iter().cloned());
+
+        for pos in covered.positions.into_inner() {
+            self.apply(canvas, Point { x: pos.x as f32, y: pos.y as f32 });
+        }
+    }
+}
+
+/// A brush which records the pixels it is applied to instead of drawing them.
+#[derive(Debug, Default)]
+struct CoverageBrush {
+    positions: RefCell<HashSet<Position>>,
+}
+
+impl<X> Brush<X> for CoverageBrush {
+    #[inline]
+    fn apply<C>(&self, _canvas: &mut C, pt: Point)
+        where C: Canvas<Pixel=X>
+    {
+        self.positions.borrow_mut().insert(Position {
+            x: pt.x.floor() as i32,
+            y: pt.y.floor() as i32,
+        });
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// LightenBrush
+////////////////////////////////////////////////////////////////////////////////
+/// A brush which replaces each color channel of the pixels it is applied to
+/// with the channel of a fixed color, if that is lighter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LightenBrush {
+    color: u32,
+}
+
+impl LightenBrush {
+    /// Returns a new `LightenBrush` which lightens pixels toward the given
+    /// color.
+    #[inline]
+    pub fn new(color: u32) -> Self {
+        LightenBrush { color }
+    }
+}
+
+impl Brush<u32> for LightenBrush {
+    #[inline]
+    fn apply<C>(&self, canvas: &mut C, pt: Point)
+        where C: Canvas<Pixel=u32>
+    {
+        self.apply_stamp(canvas, pt, 1.0, 1.0);
+    }
+
+    #[inline]
+    fn apply_stamp<C>(&self, canvas: &mut C, pt: Point, _size: f32, opacity: f32)
+        where C: Canvas<Pixel=u32>
+    {
+        let color = self.color;
+        modify_pixel(canvas, pt, |p| {
+            blend_color(p, map_color(p, color, |a, b| a.max(b)), opacity)
+        });
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// DarkenBrush
+////////////////////////////////////////////////////////////////////////////////
+/// A brush which replaces each color channel of the pixels it is applied to
+/// with the channel of a fixed color, if that is darker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DarkenBrush {
+    color: u32,
+}
+
+impl DarkenBrush {
+    /// Returns a new `DarkenBrush` which darkens pixels toward the given
+    /// color.
+    #[inline]
+    pub fn new(color: u32) -> Self {
+        DarkenBrush { color }
+    }
+}
+
+impl Brush<u32> for DarkenBrush {
+    #[inline]
+    fn apply<C>(&self, canvas: &mut C, pt: Point)
+        where C: Canvas<Pixel=u32>
+    {
+        self.apply_stamp(canvas, pt, 1.0, 1.0);
+    }
+
+    #[inline]
+    fn apply_stamp<C>(&self, canvas: &mut C, pt: Point, _size: f32, opacity: f32)
+        where C: Canvas<Pixel=u32>
+    {
+        let color = self.color;
+        modify_pixel(canvas, pt, |p| {
+            blend_color(p, map_color(p, color, |a, b| a.min(b)), opacity)
+        });
+    }
+}
@@ -16,6 +16,7 @@ use geometry::angle::angle_classify;
 use geometry::angle::AngleType;
 use geometry::Point;
 use geometry::Rect;
+use geometry::Segment;
 use utilities::clipped;
 use utilities::ordered;
 use utilities::same_sign;
@@ -39,7 +40,7 @@ pub enum Intersection {
 
 
 enum EdgeIntersection {
-    Colinear([Point; 2]),
+    Colinear(Segment),
     At(Point),
 }
 
@@ -54,7 +55,7 @@ impl RectEdgeIter {
 }
 
 impl Iterator for RectEdgeIter {
-    type Item = [Point; 2];
+    type Item = Segment;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.edge_num >= 4 {
@@ -62,22 +63,22 @@ impl Iterator for RectEdgeIter {
         } else {
             self.edge_num += 1;
             match self.edge_num {
-                1 => Some([ // Top
+                1 => Some(Segment::new( // Top
                     Point { x: self.rect.left, y: self.rect.top },
                     Point { x: self.rect.right, y: self.rect.top },
-                ]),
-                2 => Some([ // Left
+                )),
+                2 => Some(Segment::new( // Left
                     Point { x: self.rect.left, y: self.rect.top },
                     Point { x: self.rect.left, y: self.rect.bottom },
-                ]),
-                3 => Some([ // Right
+                )),
+                3 => Some(Segment::new( // Right
                     Point { x: self.rect.right, y: self.rect.top },
                     Point { x: self.rect.right, y: self.rect.bottom },
-                ]),
-                4 => Some([ // Bottom
+                )),
+                4 => Some(Segment::new( // Bottom
                     Point { x: self.rect.left, y: self.rect.bottom },
                     Point { x: self.rect.right, y: self.rect.bottom },
-                ]),
+                )),
                 _ => unreachable!(),
             }
         }
@@ -126,15 +127,21 @@ impl Iterator for RectEdgeIntersectIter {
 ///
 /// # Arguments
 ///
-/// `epa`: The endpoints of the first line segment.
+/// `epa`: The first line [`Segment`].
 ///
-/// `epb`: The endpoints of the second line segment.
+/// `epb`: The second line `Segment`.
 ///
 /// [`Point`]: ../talc/struct.Point.html
+/// [`Segment`]: struct.Segment.html
 /// [`Intersection`]: struct.Intersection.html
-pub fn intersect_segment_with_segment(epa: [Point; 2], epb: [Point; 2]) 
-    -> Intersection
+pub fn intersect_segment_with_segment<A, B>(epa: A, epb: B) -> Intersection
+    where
+        A: Into<Segment>,
+        B: Into<Segment>,
 {
+    let epa = epa.into().endpoints();
+    let epb = epb.into().endpoints();
+
     // Adapted from C implementation by Mukesh Prasad at
     // http://www.realtimerendering.com/resources/GraphicsGems/gemsii/xlines.c
 
@@ -195,14 +202,18 @@ pub fn intersect_segment_with_segment(epa: [Point; 2], epb: [Point; 2])
 ///
 /// `angle`: The line's angle with respect to the positive x-axis.
 ///
-/// `segment`: The endpoints of the line segment.
+/// `segment`: The line [`Segment`].
 ///
 /// [`Point`]: ../talc/struct.Point.html
+/// [`Segment`]: struct.Segment.html
 /// [`Intersection`]: struct.Intersection.html
-pub fn intersect_line_with_segment(pt: Point, angle: f64, segment: [Point; 2])
+pub fn intersect_line_with_segment<S>(pt: Point, angle: f64, segment: S)
     -> Intersection
+    where S: Into<Segment>
 {
-    if segment[0] == segment[1] { panic!("invalid segment"); }
+    let segment = segment.into();
+    if segment.is_degenerate() { panic!("invalid segment"); }
+    let segment = segment.endpoints();
 
     // Calculate coefficients for line equation ax + by + c = 0.
     let a = segment[1].y - segment[0].y;
@@ -279,7 +290,7 @@ pub fn intersect_line_with_segment(pt: Point, angle: f64, segment: [Point; 2])
 ///
 /// # Arguments
 ///
-/// `segment`: The endpoints of the line segment.
+/// `segment`: The line [`Segment`].
 ///
 /// `rect`: The boundary `Rect`.
 ///
@@ -288,16 +299,17 @@ pub fn intersect_line_with_segment(pt: Point, angle: f64, segment: [Point; 2])
 /// Panics if the segment's endpoints are equal.
 ///
 /// [`Rect`]: ../talc/struct.Rect.html
-pub fn extend_segment_to_rect(segment: [Point; 2], rect: Rect)
-    -> Option<[Point; 2]>
+/// [`Segment`]: struct.Segment.html
+pub fn extend_segment_to_rect<S>(segment: S, rect: Rect) -> Option<Segment>
+    where S: Into<Segment>
 {
     // Terse segment constructor.
     #[inline]
-    fn s(x1: f32, y1: f32, x2: f32, y2: f32) -> Option<[Point; 2]> {
-        Some([Point { x: x1, y: y1 },  Point { x: x2, y: y2 }])
+    fn s(x1: f32, y1: f32, x2: f32, y2: f32) -> Option<Segment> {
+        Some(Segment::new(Point { x: x1, y: y1 },  Point { x: x2, y: y2 }))
     }
 
-    let [ea, eb] = segment;
+    let Segment { start: ea, end: eb } = segment.into();
     if ea == eb {
         panic!("segment endpoints do not determine a unique line");
     }
@@ -379,15 +391,17 @@ pub fn extend_segment_to_rect(segment: [Point; 2], rect: Rect)
 ///
 /// # Arguments
 ///
-/// `segment`: The endpoints of the line segment.
+/// `segment`: The line [`Segment`].
 ///
 /// `rect`: The boundary `Rect`.
 ///
 /// [`Rect`]: ../talc/struct.Rect.html
-pub fn clip_segment_to_rect(segment: [Point; 2], rect: Rect)
-    -> Option<[Point; 2]>
+/// [`Segment`]: struct.Segment.html
+pub fn clip_segment_to_rect<S>(segment: S, rect: Rect) -> Option<Segment>
+    where S: Into<Segment>
 {
-    let [Point {x: xa, y: ya}, Point {x: xb, y: yb}] = segment;
+    let [Point {x: xa, y: ya}, Point {x: xb, y: yb}] =
+        segment.into().endpoints();
 
     // Liang-Barky line clipping algorithm. Based on the parametric line segment
     // equations for t in [0.0, 1.0]:
@@ -401,7 +415,9 @@ pub fn clip_segment_to_rect(segment: [Point; 2], rect: Rect)
     if dx == 0.0 {
         return if xa >= rect.left && xa < rect.right {
             clipped((ya, yb), rect.top, rect.bottom)
-                .map(|(t, b)| [Point {x: xa, y: t}, Point {x: xa, y: b}])
+                .map(|(t, b)| Segment::new(
+                    Point {x: xa, y: t},
+                    Point {x: xa, y: b}))
         } else {
             None
         }
@@ -409,7 +425,9 @@ pub fn clip_segment_to_rect(segment: [Point; 2], rect: Rect)
     if dy == 0.0 {
         return if ya >= rect.top && ya < rect.bottom {
             clipped((xa, xb), rect.left, rect.right)
-                .map(|(l, r)| [Point {x: l, y: ya}, Point {x: r, y: ya}])
+                .map(|(l, r)| Segment::new(
+                    Point {x: l, y: ya},
+                    Point {x: r, y: ya}))
         } else {
             None
         }
@@ -447,10 +465,10 @@ pub fn clip_segment_to_rect(segment: [Point; 2], rect: Rect)
     // the rect. Otherwise, we can use them to identify the bounds of the
     // clipped segment.
     if t.0 <= t.1 {
-        Some([
+        Some(Segment::new(
             Point { x: xa + dx * t.0, y: ya + dy * t.0 },
             Point { x: xa + dx * t.1, y: ya + dy * t.1 },
-        ])
+        ))
     } else {
         None
     }
@@ -474,7 +492,7 @@ pub fn clip_segment_to_rect(segment: [Point; 2], rect: Rect)
 /// [`Point`]: ../talc/struct.Point.html
 /// [`Rect`]: ../talc/struct.Rect.html
 pub fn clip_line_to_rect(pt: Point, angle: f64, rect: Rect)
-    -> Option<[Point; 2]>
+    -> Option<Segment>
 {
     
     let mut stepper = RectEdgeIntersectIter::new(pt, angle, rect);
@@ -499,12 +517,12 @@ pub fn clip_line_to_rect(pt: Point, angle: f64, rect: Rect)
                 if save.is_none() {
                     save = Some(pt);
                 } else if save.as_ref() != Some(&pt) {
-                    return Some([save.unwrap(), pt]);
+                    return Some(Segment::new(save.unwrap(), pt));
                 }
             },
         }
     }
 
-    save.map(|pt| [pt, pt])
+    save.map(|pt| Segment::new(pt, pt))
 
 }
@@ -11,9 +11,14 @@
 //!
 ////////////////////////////////////////////////////////////////////////////////
 
+// Public modules.
+pub mod polygon;
+
 // Internal modules.
 mod angle;
 mod line;
+mod path;
+mod segment;
 
 // Local imports.
 use utilities::clamped;
@@ -24,10 +29,16 @@ use std::ops::AddAssign;
 use std::ops::Sub;
 use std::ops::SubAssign;
 use std::ops::Mul;
+use std::ops::MulAssign;
 use std::ops::Div;
+use std::ops::DivAssign;
 use std::ops::Neg;
 use std::f32;
 
+/// The largest relative difference between two coordinates which are
+/// considered nearly equal by `Point::nearly_equal`.
+const POINT_EPSILON: f32 = 16.0 * f32::EPSILON;
+
 // Exports.
 pub use self::line::clip_line_to_rect;
 pub use self::line::clip_segment_to_rect;
@@ -35,6 +46,12 @@ pub use self::line::extend_segment_to_rect;
 pub use self::line::intersect_line_with_segment;
 pub use self::line::intersect_segment_with_segment;
 pub use self::line::Intersection;
+pub use self::path::Path;
+pub use self::path::PathCommand;
+pub use self::polygon::BooleanOp;
+pub use self::polygon::FillRule;
+pub use self::polygon::Orientation;
+pub use self::segment::Segment;
 
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -102,6 +119,86 @@ impl Point {
     pub fn round(self) -> Position {
         Position { x: self.x.round() as i32, y: self.y.round() as i32 }
     }
+
+    /// Returns the dot product of the point with another, treating both as
+    /// vectors.
+    #[inline]
+    pub fn dot(self, other: Point) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Returns the 2-dimensional cross product of the point with another,
+    /// treating both as vectors. This is the z-component of their 3-dimensional
+    /// cross product, and is positive if `other` lies clockwise from `self` as
+    /// seen with the y-axis pointing down.
+    #[inline]
+    pub fn cross(self, other: Point) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Returns the length of the point treated as a vector.
+    #[inline]
+    pub fn length(self) -> f32 {
+        self.x.hypot(self.y)
+    }
+
+    /// Returns the squared length of the point treated as a vector.
+    #[inline]
+    pub fn length_squared(self) -> f32 {
+        self.dot(self)
+    }
+
+    /// Returns the point scaled to a length of `1.0`, or the zero vector if
+    /// the point is at the origin.
+    #[inline]
+    pub fn normalized(self) -> Point {
+        let length = self.length();
+        if length == 0.0 { Point::default() } else { self / length }
+    }
+
+    /// Returns the point rotated about the origin by the given angle in
+    /// radians. Positive angles rotate clockwise as seen with the y-axis
+    /// pointing down.
+    #[inline]
+    pub fn rotated(self, angle: f32) -> Point {
+        let (sin, cos) = angle.sin_cos();
+        Point {
+            x: self.x * cos - self.y * sin,
+            y: self.x * sin + self.y * cos,
+        }
+    }
+
+    /// Returns the point rotated a quarter turn about the origin. This is the
+    /// same as `rotated(PI / 2.0)`, but exact.
+    #[inline]
+    pub fn perpendicular(self) -> Point {
+        Point { x: -self.y, y: self.x }
+    }
+
+    /// Performs a linear interpolation between the point and another,
+    /// returning the point located at the ratio given by `amount`, which is
+    /// clamped between 0 and 1.
+    #[inline]
+    pub fn lerp(self, other: Point, amount: f32) -> Point {
+        self + (other - self) * clamped(amount, 0.0, 1.0)
+    }
+
+    /// Returns the distance between the point and another.
+    #[inline]
+    pub fn distance(self, other: Point) -> f32 {
+        (other - self).length()
+    }
+
+    /// Returns true if the coordinates of the point and another are nearly
+    /// equal. Coordinates may differ by a few multiples of the `f32` rounding
+    /// error relative to their magnitude, or to `1.0` for coordinates smaller
+    /// than that.
+    #[inline]
+    pub fn nearly_equal(self, other: Point) -> bool {
+        let close = |a: f32, b: f32| a == b || (a - b).abs()
+            <= POINT_EPSILON * a.abs().max(b.abs()).max(1.0);
+        close(self.x, other.x) && close(self.y, other.y)
+    }
 }
 
 // Numerical operator traits
@@ -154,6 +251,46 @@ impl Div<Scale> for Point {
     }
 }
 
+impl Mul<f32> for Point {
+    type Output = Point;
+    #[inline]
+    fn mul(self, other: f32) -> Point {
+        Point { x: self.x * other, y: self.y * other }
+    }
+}
+
+impl Mul<Point> for f32 {
+    type Output = Point;
+    #[inline]
+    fn mul(self, other: Point) -> Point {
+        Point { x: self * other.x, y: self * other.y }
+    }
+}
+
+impl MulAssign<f32> for Point {
+    #[inline]
+    fn mul_assign(&mut self, other: f32) {
+        self.x *= other;
+        self.y *= other;
+    }
+}
+
+impl Div<f32> for Point {
+    type Output = Point;
+    #[inline]
+    fn div(self, other: f32) -> Point {
+        Point { x: self.x / other, y: self.y / other }
+    }
+}
+
+impl DivAssign<f32> for Point {
+    #[inline]
+    fn div_assign(&mut self, other: f32) {
+        self.x /= other;
+        self.y /= other;
+    }
+}
+
 impl Neg for Point {
     type Output = Point;
     #[inline]
@@ -315,6 +452,8 @@ impl From<(f32, f32)> for Scale {
 ////////////////////////////////////////////////////////////////////////////////
 // Rect
 ////////////////////////////////////////////////////////////////////////////////
+/// An axis-aligned rectangle. The left and top edges are inclusive, and the
+/// right and bottom edges are exclusive.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Rect {
     pub left: f32,
@@ -324,6 +463,30 @@ pub struct Rect {
 }
 
 impl Rect {
+    /// Returns the smallest `Rect` with the given points at opposite corners.
+    #[inline]
+    pub fn from_points(a: Point, b: Point) -> Self {
+        Rect {
+            left: a.x.min(b.x),
+            top: a.y.min(b.y),
+            right: a.x.max(b.x),
+            bottom: a.y.max(b.y),
+        }
+    }
+
+    /// Returns a new `Rect` of the given `(width, height)` size centered on the
+    /// given point.
+    #[inline]
+    pub fn from_center_size(center: Point, size: (f32, f32)) -> Self {
+        let (half_w, half_h) = (size.0 / 2.0, size.1 / 2.0);
+        Rect {
+            left: center.x - half_w,
+            top: center.y - half_h,
+            right: center.x + half_w,
+            bottom: center.y + half_h,
+        }
+    }
+
     #[inline]
     pub fn top_left(&self) -> Point {
         Point { x: self.left, y: self.top }
@@ -363,6 +526,297 @@ impl Rect {
     pub fn size(&self) -> (f32, f32) {
         (self.right - self.left, self.bottom - self.top)
     }
+
+    /// Returns the width of the `Rect`.
+    #[inline]
+    pub fn width(&self) -> f32 {
+        self.right - self.left
+    }
+
+    /// Returns the height of the `Rect`.
+    #[inline]
+    pub fn height(&self) -> f32 {
+        self.bottom - self.top
+    }
+
+    /// Returns the point at the center of the `Rect`.
+    #[inline]
+    pub fn center(&self) -> Point {
+        Point {
+            x: (self.left + self.right) / 2.0,
+            y: (self.top + self.bottom) / 2.0,
+        }
+    }
+
+    /// Returns the area of the `Rect`, or `0.0` if it is empty.
+    #[inline]
+    pub fn area(&self) -> f32 {
+        if self.is_empty() { 0.0 } else { self.width() * self.height() }
+    }
+
+    /// Returns `true` if the `Rect` contains no points.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !(self.left < self.right && self.top < self.bottom)
+    }
+
+    /// Returns `true` if the `Rect` shares any points with the given `Rect`.
+    #[inline]
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Returns the `Rect` containing the points shared by the `Rect` and the
+    /// given `Rect`, or `None` if they share no points.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let rect = Rect {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right),
+            bottom: self.bottom.min(other.bottom),
+        };
+        if rect.is_empty() { None } else { Some(rect) }
+    }
+
+    /// Returns the smallest `Rect` containing both the `Rect` and the given
+    /// `Rect`.
+    #[inline]
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            left: self.left.min(other.left),
+            top: self.top.min(other.top),
+            right: self.right.max(other.right),
+            bottom: self.bottom.max(other.bottom),
+        }
+    }
+
+    /// Returns the `Rect` with its left and right edges moved outward by `dx`
+    /// and its top and bottom edges moved outward by `dy`.
+    #[inline]
+    pub fn inflated(&self, dx: f32, dy: f32) -> Rect {
+        Rect {
+            left: self.left - dx,
+            top: self.top - dy,
+            right: self.right + dx,
+            bottom: self.bottom + dy,
+        }
+    }
+
+    /// Returns the `Rect` with its left and right edges moved inward by `dx`
+    /// and its top and bottom edges moved inward by `dy`. The result may be
+    /// empty.
+    #[inline]
+    pub fn deflated(&self, dx: f32, dy: f32) -> Rect {
+        self.inflated(-dx, -dy)
+    }
+
+    /// Returns the `Rect` moved by the given offset.
+    #[inline]
+    pub fn translated(&self, offset: Point) -> Rect {
+        Rect {
+            left: self.left + offset.x,
+            top: self.top + offset.y,
+            right: self.right + offset.x,
+            bottom: self.bottom + offset.y,
+        }
+    }
+
+    /// Returns the smallest [`IRect`] containing the `Rect`, rounding the left
+    /// and top edges down and the right and bottom edges up.
+    ///
+    /// [`IRect`]: struct.IRect.html
+    #[inline]
+    pub fn round(&self) -> IRect {
+        IRect::from(*self)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// IRect
+////////////////////////////////////////////////////////////////////////////////
+/// An axis-aligned rectangle of pixels. The left and top edges are inclusive,
+/// and the right and bottom edges are exclusive.
+///
+/// An `IRect` converted from a [`Rect`] contains every pixel that the `Rect`
+/// overlaps, so that every point within the `Rect` is aligned to one of its
+/// pixels.
+///
+/// [`Rect`]: struct.Rect.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct IRect {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl IRect {
+    /// Returns the smallest `IRect` containing both of the given positions.
+    #[inline]
+    pub fn from_positions(a: Position, b: Position) -> Self {
+        IRect {
+            left: a.x.min(b.x),
+            top: a.y.min(b.y),
+            right: a.x.max(b.x) + 1,
+            bottom: a.y.max(b.y) + 1,
+        }
+    }
+
+    #[inline]
+    pub fn top_left(&self) -> Position {
+        Position { x: self.left, y: self.top }
+    }
+
+    #[inline]
+    pub fn bottom_right(&self) -> Position {
+        Position { x: self.right, y: self.bottom }
+    }
+
+    /// Returns the number of columns in the `IRect`.
+    #[inline]
+    pub fn width(&self) -> i32 {
+        (self.right - self.left).max(0)
+    }
+
+    /// Returns the number of rows in the `IRect`.
+    #[inline]
+    pub fn height(&self) -> i32 {
+        (self.bottom - self.top).max(0)
+    }
+
+    /// Returns the number of pixels in the `IRect`.
+    #[inline]
+    pub fn area(&self) -> usize {
+        self.width() as usize * self.height() as usize
+    }
+
+    /// Returns `true` if the `IRect` contains no pixels.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.left >= self.right || self.top >= self.bottom
+    }
+
+    #[inline]
+    pub fn contains(&self, pos: Position) -> bool {
+        pos.x >= self.left && pos.x < self.right
+            && pos.y >= self.top && pos.y < self.bottom
+    }
+
+    /// Returns the `IRect` containing the pixels shared by the `IRect` and the
+    /// given `IRect`, or `None` if they share no pixels.
+    pub fn intersection(&self, other: &IRect) -> Option<IRect> {
+        let rect = IRect {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right),
+            bottom: self.bottom.min(other.bottom),
+        };
+        if rect.is_empty() { None } else { Some(rect) }
+    }
+
+    /// Returns an iterator over the positions of the pixels in the `IRect`,
+    /// from left to right within each row, and from the top row to the
+    /// bottom.
+    #[inline]
+    pub fn positions(&self) -> PositionIter {
+        PositionIter::new(*self)
+    }
 }
 
+// Conversion traits
+
+impl From<Rect> for IRect {
+    /// Returns the `IRect` of pixels overlapped by the given `Rect`. Empty
+    /// `Rect`s give empty `IRect`s.
+    #[inline]
+    fn from(rect: Rect) -> Self {
+        if rect.is_empty() {
+            let (x, y) = (rect.left.floor() as i32, rect.top.floor() as i32);
+            return IRect { left: x, top: y, right: x, bottom: y };
+        }
+        IRect {
+            left: rect.left.floor() as i32,
+            top: rect.top.floor() as i32,
+            right: rect.right.ceil() as i32,
+            bottom: rect.bottom.ceil() as i32,
+        }
+    }
+}
+
+impl From<IRect> for Rect {
+    #[inline]
+    fn from(rect: IRect) -> Self {
+        Rect {
+            left: rect.left as f32,
+            top: rect.top as f32,
+            right: rect.right as f32,
+            bottom: rect.bottom as f32,
+        }
+    }
+}
+
+impl IntoIterator for IRect {
+    type Item = Position;
+    type IntoIter = PositionIter;
+
+    #[inline]
+    fn into_iter(self) -> PositionIter {
+        PositionIter::new(self)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// PositionIter
+////////////////////////////////////////////////////////////////////////////////
+/// An iterator over the pixel positions of an [`IRect`] in row-major order.
+///
+/// [`IRect`]: struct.IRect.html
+#[derive(Debug, Clone)]
+pub struct PositionIter {
+    rect: IRect,
+    next: Position,
+}
+
+impl PositionIter {
+    #[inline]
+    fn new(rect: IRect) -> Self {
+        PositionIter { rect, next: rect.top_left() }
+    }
+
+    /// Returns the number of positions remaining in the iterator.
+    #[inline]
+    fn remaining(&self) -> usize {
+        if self.rect.is_empty() || self.next.y >= self.rect.bottom {
+            return 0;
+        }
+        let rows = (self.rect.bottom - self.next.y - 1) as usize;
+        rows * self.rect.width() as usize
+            + (self.rect.right - self.next.x) as usize
+    }
+}
+
+impl Iterator for PositionIter {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Position> {
+        if self.remaining() == 0 { return None; }
+
+        let current = self.next;
+        self.next.x += 1;
+        if self.next.x >= self.rect.right {
+            self.next.x = self.rect.left;
+            self.next.y += 1;
+        }
+        Some(current)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
+}
 
+impl ExactSizeIterator for PositionIter {}
@@ -0,0 +1,388 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Paths of lines and quadratic curves.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use geometry::Point;
+use geometry::Rect;
+use geometry::polygon;
+
+// Standard library imports.
+use std::f32;
+
+
+/// The number of samples taken along each axis of a pixel when computing the
+/// coverage of a filled path.
+const MASK_SAMPLES: usize = 4;
+
+/// The maximum distance between a curve and the polyline approximating it when
+/// computing the coverage of a filled path.
+const MASK_TOLERANCE: f32 = 0.1;
+
+/// The maximum distance between a cubic curve and the quadratic curves
+/// approximating it.
+const CUBIC_TOLERANCE: f32 = 0.1;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// PathCommand
+////////////////////////////////////////////////////////////////////////////////
+/// A single drawing command of a [`Path`].
+///
+/// [`Path`]: struct.Path.html
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathCommand {
+    /// Begins a new contour at the given point.
+    MoveTo(Point),
+    /// Draws a line from the current point to the given point.
+    LineTo(Point),
+    /// Draws a quadratic curve from the current point to the second given
+    /// point, using the first as its control point.
+    QuadTo(Point, Point),
+    /// Closes the current contour with a line back to its first point.
+    Close,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Path
+////////////////////////////////////////////////////////////////////////////////
+/// A sequence of contours made of lines and quadratic curves.
+///
+/// Paths may be stroked by drawing their [`flattened`] contours, or filled
+/// using their [`mask`], which treats every contour as closed and uses the
+/// nonzero winding rule.
+///
+/// [`flattened`]: #method.flattened
+/// [`mask`]: #method.mask
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Path {
+    commands: Vec<PathCommand>,
+}
+
+impl Path {
+    /// Returns a new empty `Path`.
+    #[inline]
+    pub fn new() -> Self {
+        Path { commands: Vec::new() }
+    }
+
+    /// Begins a new contour at the given point.
+    #[inline]
+    pub fn move_to(&mut self, pt: Point) {
+        self.commands.push(PathCommand::MoveTo(pt));
+    }
+
+    /// Adds a line from the current point to the given point.
+    #[inline]
+    pub fn line_to(&mut self, pt: Point) {
+        self.commands.push(PathCommand::LineTo(pt));
+    }
+
+    /// Adds a quadratic curve from the current point to the given end point.
+    #[inline]
+    pub fn quad_to(&mut self, control: Point, end: Point) {
+        self.commands.push(PathCommand::QuadTo(control, end));
+    }
+
+    /// Adds a cubic curve from the current point to the given end point. The
+    /// curve is approximated by quadratic curves.
+    pub fn cubic_to(&mut self, c1: Point, c2: Point, end: Point) {
+        let start = self.current_point().unwrap_or(c1);
+
+        // The error of approximating a cubic with a single quadratic is
+        // proportional to the cubic's third derivative, which shrinks with the
+        // cube of the number of pieces it is split into.
+        let error = (end - c2 * 3.0 + c1 * 3.0 - start).length()
+            * 3.0f32.sqrt() / 36.0;
+        let pieces = (error / CUBIC_TOLERANCE).cbrt().ceil().max(1.0) as u32;
+
+        let at = |t: f32| {
+            let u = 1.0 - t;
+            start * (u * u * u)
+                + c1 * (3.0 * u * u * t)
+                + c2 * (3.0 * u * t * t)
+                + end * (t * t * t)
+        };
+        // The derivative at the given parameter, scaled by a third of the
+        // length of a piece.
+        let tangent = |t: f32| {
+            let u = 1.0 - t;
+            ((c1 - start) * (u * u)
+                + (c2 - c1) * (2.0 * u * t)
+                + (end - c2) * (t * t))
+                / pieces as f32
+        };
+
+        for n in 0..pieces {
+            let t0 = n as f32 / pieces as f32;
+            let t1 = (n + 1) as f32 / pieces as f32;
+            let (p0, p3) = (at(t0), if n + 1 == pieces { end } else { at(t1) });
+            let (p1, p2) = (p0 + tangent(t0), p3 - tangent(t1));
+
+            // The control point of a quadratic approximating a cubic lies at
+            // the average of the extrapolated control points from either end.
+            self.quad_to((p1 * 3.0 + p2 * 3.0 - p0 - p3) / 4.0, p3);
+        }
+    }
+
+    /// Closes the current contour.
+    #[inline]
+    pub fn close(&mut self) {
+        self.commands.push(PathCommand::Close);
+    }
+
+    /// Appends the contours of the given path.
+    #[inline]
+    pub fn extend(&mut self, other: &Path) {
+        self.commands.extend_from_slice(&other.commands);
+    }
+
+    /// Returns the commands of the path.
+    #[inline]
+    pub fn commands(&self) -> &[PathCommand] {
+        &self.commands[..]
+    }
+
+    /// Returns `true` if the path has no commands.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Returns the end point of the last command, or the first point of the
+    /// last contour if it has been closed.
+    fn current_point(&self) -> Option<Point> {
+        let mut closed = false;
+        for &command in self.commands.iter().rev() {
+            match command {
+                PathCommand::MoveTo(pt)        => return Some(pt),
+                PathCommand::LineTo(pt)        |
+                PathCommand::QuadTo(_, pt)     if !closed => return Some(pt),
+                PathCommand::Close             => closed = true,
+                _                              => (),
+            }
+        }
+        None
+    }
+
+    /// Returns the path with the given function applied to each of its points.
+    /// Affine transformations are applied exactly.
+    pub fn transformed<F>(&self, transform: F) -> Path
+        where F: Fn(Point) -> Point
+    {
+        let commands = self.commands.iter()
+            .map(|&command| match command {
+                PathCommand::MoveTo(pt)        => {
+                    PathCommand::MoveTo(transform(pt))
+                },
+                PathCommand::LineTo(pt)        => {
+                    PathCommand::LineTo(transform(pt))
+                },
+                PathCommand::QuadTo(ctrl, end) => {
+                    PathCommand::QuadTo(transform(ctrl), transform(end))
+                },
+                PathCommand::Close             => PathCommand::Close,
+            })
+            .collect();
+        Path { commands }
+    }
+
+    /// Returns the path moved by the given offset.
+    #[inline]
+    pub fn translated(&self, offset: Point) -> Path {
+        self.transformed(|pt| pt + offset)
+    }
+
+    /// Returns a [`Rect`] containing the path, or `None` if the path has no
+    /// points. The `Rect` contains the control points of curves, so it may be
+    /// larger than the path itself.
+    ///
+    /// [`Rect`]: struct.Rect.html
+    pub fn bounds(&self) -> Option<Rect> {
+        let points: Vec<Point> = self.commands.iter()
+            .flat_map(|&command| match command {
+                PathCommand::MoveTo(pt)        |
+                PathCommand::LineTo(pt)        => vec![pt],
+                PathCommand::QuadTo(ctrl, end) => vec![ctrl, end],
+                PathCommand::Close             => vec![],
+            })
+            .collect();
+        polygon::bounds(&points)
+    }
+
+    /// Returns the contours of the path as polylines, with curves approximated
+    /// to within the given distance. Closed contours end with their first
+    /// point.
+    pub fn flattened(&self, tolerance: f32) -> Vec<Vec<Point>> {
+        let mut contours: Vec<Vec<Point>> = Vec::new();
+        let mut current: Vec<Point> = Vec::new();
+        for &command in &self.commands {
+            match command {
+                PathCommand::MoveTo(pt)        => {
+                    if current.len() > 1 { contours.push(current); }
+                    current = vec![pt];
+                },
+                PathCommand::LineTo(pt)        => {
+                    let start = current.last().cloned().unwrap_or(pt);
+                    if current.is_empty() { current.push(start); }
+                    current.push(pt);
+                },
+                PathCommand::QuadTo(ctrl, end) => {
+                    let start = current.last().cloned().unwrap_or(ctrl);
+                    if current.is_empty() { current.push(start); }
+                    flatten_quad(&mut current, start, ctrl, end, tolerance);
+                },
+                PathCommand::Close             => {
+                    if let Some(&first) = current.first() {
+                        current.push(first);
+                    }
+                    if current.len() > 1 { contours.push(current); }
+                    current = Vec::new();
+                },
+            }
+        }
+        if current.len() > 1 { contours.push(current); }
+        contours
+    }
+
+    /// Returns a function giving the coverage of the filled path over the
+    /// pixel centered at a given point, suitable for use as a fill or clip
+    /// mask. Only pixels overlapping the given `Rect` are sampled, and all
+    /// others are given no coverage.
+    pub fn mask(&self, rect: Rect) -> impl Fn(Point) -> f32 {
+        let rows = EdgeRows::new(&self.flattened(MASK_TOLERANCE), rect);
+
+        move |center| {
+            // Pixels outside of the sampled bounds are not sampled.
+            let bounds = match rows.bounds {
+                Some(bounds) => bounds,
+                None         => return 0.0,
+            };
+            if center.x + 0.5 <= bounds.left || center.x - 0.5 >= bounds.right
+                || center.y + 0.5 <= bounds.top
+                || center.y - 0.5 >= bounds.bottom
+            {
+                return 0.0;
+            }
+
+            let step = 1.0 / MASK_SAMPLES as f32;
+            let mut inside = 0;
+            for j in 0..MASK_SAMPLES {
+                let y = center.y - 0.5 + (j as f32 + 0.5) * step;
+                let edges = rows.edges_at(y);
+                for i in 0..MASK_SAMPLES {
+                    let sample = Point {
+                        x: center.x - 0.5 + (i as f32 + 0.5) * step,
+                        y,
+                    };
+                    let winding: i32 = edges.iter()
+                        .map(|&edge| polygon::edge_winding(edge, sample))
+                        .sum();
+                    if winding != 0 { inside += 1; }
+                }
+            }
+            inside as f32 / (MASK_SAMPLES * MASK_SAMPLES) as f32
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// EdgeRows
+////////////////////////////////////////////////////////////////////////////////
+/// The edges of a filled path, grouped by the rows of pixels they cross.
+struct EdgeRows {
+    /// The bounds of the path's edges within the sampled area, or `None` if
+    /// there are none.
+    bounds: Option<Rect>,
+    /// The topmost row with edges.
+    top: i32,
+    /// The edges overlapping each row, starting from the top row.
+    rows: Vec<Vec<(Point, Point)>>,
+}
+
+impl EdgeRows {
+    /// Returns the `EdgeRows` of the given contours within the given `Rect`.
+    /// Every contour is closed when filling, and the windings of all contours
+    /// are combined.
+    fn new(contours: &[Vec<Point>], rect: Rect) -> Self {
+        let points: Vec<Point> = contours.iter()
+            .flat_map(|contour| contour.iter().cloned())
+            .collect();
+        let bounds = polygon::bounds(&points)
+            .and_then(|bounds| bounds.intersection(&rect));
+        let (top, bottom) = match bounds {
+            Some(bounds) => (
+                bounds.top.floor() as i32,
+                bounds.bottom.floor() as i32),
+            None         => (0, -1),
+        };
+
+        let mut rows = vec![Vec::new(); (bottom - top + 1) as usize];
+        for contour in contours {
+            let closing = (contour[contour.len() - 1], contour[0]);
+            let edges = contour.windows(2)
+                .map(|w| (w[0], w[1]))
+                .chain(Some(closing));
+            // Horizontal edges never cross a sample row, and edges outside
+            // of the sampled rows are clamped to them.
+            for (a, b) in edges.filter(|&(a, b)| a.y != b.y) {
+                let first = (a.y.min(b.y).floor() as i32).max(top) - top;
+                let last = (a.y.max(b.y).floor() as i32).min(bottom) - top;
+                if first > last { continue; }
+                for row in &mut rows[first as usize..=last as usize] {
+                    row.push((a, b));
+                }
+            }
+        }
+
+        EdgeRows { bounds, top, rows }
+    }
+
+    /// Returns the edges which may cross the horizontal line at the given
+    /// position.
+    #[inline]
+    fn edges_at(&self, y: f32) -> &[(Point, Point)] {
+        let row = y.floor() as i32 - self.top;
+        if row < 0 { return &[]; }
+        self.rows.get(row as usize).map(|row| &row[..]).unwrap_or(&[])
+    }
+}
+
+
+/// Appends the points approximating the given quadratic curve to the given
+/// polyline, excluding the start point.
+fn flatten_quad(
+    polyline: &mut Vec<Point>,
+    start: Point,
+    ctrl: Point,
+    end: Point,
+    tolerance: f32)
+{
+    // The curve's deviation from its chord shrinks with the square of the
+    // number of pieces.
+    let dx = start.x - 2.0 * ctrl.x + end.x;
+    let dy = start.y - 2.0 * ctrl.y + end.y;
+    let deviation = (dx * dx + dy * dy).sqrt() / 4.0;
+    let pieces = (deviation / tolerance.max(f32::EPSILON))
+        .sqrt()
+        .ceil()
+        .max(1.0) as u32;
+
+    for n in 1..=pieces {
+        let t = n as f32 / pieces as f32;
+        let u = 1.0 - t;
+        polyline.push(Point {
+            x: u * u * start.x + 2.0 * u * t * ctrl.x + t * t * end.x,
+            y: u * u * start.y + 2.0 * u * t * ctrl.y + t * t * end.y,
+        });
+    }
+}
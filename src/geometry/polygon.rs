@@ -0,0 +1,534 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Polygon algorithms.
+//!
+//! Polygons are given as slices of their vertices, and are implicitly closed by
+//! an edge from the last vertex back to the first. Orientations are described
+//! as seen with the y-axis pointing down, as on a canvas.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use geometry::Point;
+use geometry::Rect;
+
+// Standard library imports.
+use std::f32::consts::PI;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// FillRule
+////////////////////////////////////////////////////////////////////////////////
+/// A rule determining which points lie inside of a polygon whose edges cross
+/// or overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FillRule {
+    /// Points are inside if a ray from them crosses the polygon's edges an odd
+    /// number of times.
+    EvenOdd,
+    /// Points are inside if the polygon winds around them a nonzero number of
+    /// times.
+    NonZero,
+}
+
+impl Default for FillRule {
+    #[inline]
+    fn default() -> Self {
+        FillRule::NonZero
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Orientation
+////////////////////////////////////////////////////////////////////////////////
+/// The direction in which the vertices of a polygon are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    /// The vertices are ordered clockwise.
+    Clockwise,
+    /// The vertices are ordered counterclockwise.
+    Counterclockwise,
+    /// The polygon has no area.
+    Degenerate,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// BooleanOp
+////////////////////////////////////////////////////////////////////////////////
+/// A set operation combining the regions of two polygons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BooleanOp {
+    /// The region covered by either polygon.
+    Union,
+    /// The region covered by both polygons.
+    Intersection,
+    /// The region covered by the first polygon but not the second.
+    Difference,
+    /// The region covered by exactly one of the polygons.
+    Xor,
+}
+
+
+/// A directed edge between two points.
+type Edge = (Point, Point);
+
+/// Returns an iterator over the edges of the given polygon, including the edge
+/// closing it.
+#[inline]
+fn edges<'p>(points: &'p [Point]) -> impl Iterator<Item=Edge> + 'p {
+    let closing = points.last().cloned().into_iter()
+        .zip(points.first().cloned());
+    points.windows(2)
+        .map(|w| (w[0], w[1]))
+        .chain(closing)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// signed_area
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the area of the given polygon, which is positive if its vertices are
+/// ordered clockwise and negative if they are ordered counterclockwise.
+///
+/// The areas of overlapping parts of self-intersecting polygons are combined
+/// according to their winding.
+pub fn signed_area(points: &[Point]) -> f32 {
+    edges(points).map(|(a, b)| a.cross(b)).sum::<f32>() / 2.0
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// centroid
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the center of mass of the given polygon, or `None` if it has no
+/// area.
+pub fn centroid(points: &[Point]) -> Option<Point> {
+    let area = signed_area(points);
+    if area == 0.0 { return None; }
+
+    let sum = edges(points).fold(Point::default(), |sum, (a, b)| {
+        sum + (a + b) * a.cross(b)
+    });
+    Some(sum / (6.0 * area))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// orientation
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the [`Orientation`] of the given polygon's vertices.
+///
+/// [`Orientation`]: enum.Orientation.html
+pub fn orientation(points: &[Point]) -> Orientation {
+    let area = signed_area(points);
+    if area > 0.0 {
+        Orientation::Clockwise
+    } else if area < 0.0 {
+        Orientation::Counterclockwise
+    } else {
+        Orientation::Degenerate
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// is_convex
+////////////////////////////////////////////////////////////////////////////////
+/// Returns `true` if the given polygon is convex. Polygons with colinear or
+/// repeated vertices may be convex, but polygons without area are not.
+pub fn is_convex(points: &[Point]) -> bool {
+    if points.len() < 3 || signed_area(points) == 0.0 { return false; }
+
+    // A convex polygon turns in only one direction, and turns only once in
+    // total. The second condition rejects self-intersecting polygons such as
+    // stars.
+    let n = points.len();
+    let mut direction = 0.0;
+    let mut turning = 0.0;
+    for i in 0..n {
+        let a = points[i];
+        let b = points[(i + 1) % n];
+        let c = points[(i + 2) % n];
+        let (incoming, outgoing) = (b - a, c - b);
+
+        let cross = incoming.cross(outgoing);
+        if cross * direction < 0.0 { return false; }
+        if cross != 0.0 { direction = cross; }
+        turning += cross.atan2(incoming.dot(outgoing));
+    }
+    turning.abs() < 3.0 * PI
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// winding_number
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the number of times the given polygon winds clockwise around the
+/// given point. Counterclockwise windings are counted as negative.
+pub fn winding_number(points: &[Point], pt: Point) -> i32 {
+    edges(points).map(|edge| edge_winding(edge, pt)).sum()
+}
+
+/// Returns the contribution of the given polygon edge to the winding number of
+/// the given point.
+#[inline]
+pub(in geometry) fn edge_winding((a, b): Edge, pt: Point) -> i32 {
+    // Count crossings of a ray extending rightward from the point.
+    let cross = (b - a).cross(pt - a);
+    if a.y <= pt.y && b.y > pt.y && cross > 0.0 {
+        1
+    } else if a.y > pt.y && b.y <= pt.y && cross < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// contains
+////////////////////////////////////////////////////////////////////////////////
+/// Returns `true` if the given point lies inside of the given polygon according
+/// to the given [`FillRule`].
+///
+/// [`FillRule`]: enum.FillRule.html
+#[inline]
+pub fn contains(points: &[Point], pt: Point, rule: FillRule) -> bool {
+    let winding = winding_number(points, pt);
+    match rule {
+        FillRule::EvenOdd => winding % 2 != 0,
+        FillRule::NonZero => winding != 0,
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// bounds
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the smallest [`Rect`] containing the given points, or `None` if
+/// there are no points.
+///
+/// [`Rect`]: ../struct.Rect.html
+pub fn bounds(points: &[Point]) -> Option<Rect> {
+    let first = match points.first() {
+        Some(&first) => first,
+        None         => return None,
+    };
+    Some(points[1..].iter().fold(
+        Rect::from_points(first, first),
+        |rect, &pt| rect.union(&Rect::from_points(pt, pt))))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// clip_to_rect
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the part of the given polygon lying within the given [`Rect`], or an
+/// empty polygon if there is none.
+///
+/// As with the pixels of a canvas, the left and top edges of the `Rect` are
+/// inclusive and its right and bottom edges are exclusive, so a polygon which
+/// only touches the right or bottom edge gives an empty polygon.
+///
+/// Concave polygons whose clipped parts are disjoint produce a single polygon
+/// joining the parts along the edges of the `Rect`.
+///
+/// [`Rect`]: ../struct.Rect.html
+pub fn clip_to_rect(points: &[Point], rect: Rect) -> Vec<Point> {
+    // Sutherland-Hodgman polygon clipping, against each edge in turn.
+    let clipped = clip_to_boundary(points,
+        |pt| pt.x >= rect.left,
+        |a, b| at_x(a, b, rect.left));
+    let clipped = clip_to_boundary(&clipped,
+        |pt| pt.y >= rect.top,
+        |a, b| at_y(a, b, rect.top));
+    let clipped = clip_to_boundary(&clipped,
+        |pt| pt.x < rect.right,
+        |a, b| at_x(a, b, rect.right));
+    let mut clipped = clip_to_boundary(&clipped,
+        |pt| pt.y < rect.bottom,
+        |a, b| at_y(a, b, rect.bottom));
+
+    // Vertices on the edges of the `Rect` may be repeated by the clipping.
+    clipped.dedup();
+    while clipped.len() > 1 && clipped.first() == clipped.last() {
+        clipped.pop();
+    }
+    if clipped.len() < 3 { Vec::new() } else { clipped }
+}
+
+/// Returns the part of the given polygon lying inside of a boundary line.
+fn clip_to_boundary<I, X>(points: &[Point], inside: I, intersect: X)
+    -> Vec<Point>
+    where
+        I: Fn(Point) -> bool,
+        X: Fn(Point, Point) -> Point,
+{
+    let mut clipped = Vec::with_capacity(points.len() + 1);
+    for (a, b) in edges(points) {
+        match (inside(a), inside(b)) {
+            (true,  true)  => clipped.push(b),
+            (true,  false) => clipped.push(intersect(a, b)),
+            (false, true)  => {
+                clipped.push(intersect(a, b));
+                clipped.push(b);
+            },
+            (false, false) => (),
+        }
+    }
+    clipped
+}
+
+/// Returns the point at which the given edge crosses the given x-coordinate.
+#[inline]
+fn at_x(a: Point, b: Point, x: f32) -> Point {
+    let pt = a + (b - a) * ((x - a.x) / (b.x - a.x));
+    Point { x, y: pt.y }
+}
+
+/// Returns the point at which the given edge crosses the given y-coordinate.
+#[inline]
+fn at_y(a: Point, b: Point, y: f32) -> Point {
+    let pt = a + (b - a) * ((y - a.y) / (b.y - a.y));
+    Point { x: pt.x, y }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// boolean
+////////////////////////////////////////////////////////////////////////////////
+/// Combines the regions of the given polygons using the given [`BooleanOp`].
+/// Returns the contours of the resulting region.
+///
+/// The polygons may be concave and ordered in either direction, but must not
+/// intersect themselves. Contours enclosing the region are ordered clockwise,
+/// and contours of holes within it are ordered counterclockwise, so that the
+/// region can be filled using the nonzero [`FillRule`].
+///
+/// [`BooleanOp`]: enum.BooleanOp.html
+/// [`FillRule`]: enum.FillRule.html
+pub fn boolean(a: &[Point], b: &[Point], op: BooleanOp) -> Vec<Vec<Point>> {
+    let a = clockwise(a);
+    let b = clockwise(b);
+    let (a_edges, b_edges) = split_edges(&a, &b);
+
+    // Keep the pieces of each polygon's boundary which bound the result,
+    // reversing them where the result lies on their outer side. Boundaries
+    // shared by both polygons are taken from the first.
+    let mut selected = Vec::new();
+    for &edge in &a_edges {
+        let keep = match (op, classify(edge, &b_edges, &b)) {
+            (BooleanOp::Union,        EdgeSide::Outside)  |
+            (BooleanOp::Union,        EdgeSide::Same)     |
+            (BooleanOp::Intersection, EdgeSide::Inside)   |
+            (BooleanOp::Intersection, EdgeSide::Same)     |
+            (BooleanOp::Difference,   EdgeSide::Outside)  |
+            (BooleanOp::Difference,   EdgeSide::Opposite) |
+            (BooleanOp::Xor,          EdgeSide::Outside)  => Some(edge),
+            (BooleanOp::Xor,          EdgeSide::Inside)   => {
+                Some((edge.1, edge.0))
+            },
+            _ => None,
+        };
+        selected.extend(keep);
+    }
+    for &edge in &b_edges {
+        let keep = match (op, classify(edge, &a_edges, &a)) {
+            (BooleanOp::Union,        EdgeSide::Outside) |
+            (BooleanOp::Intersection, EdgeSide::Inside)  |
+            (BooleanOp::Xor,          EdgeSide::Outside) => Some(edge),
+            (BooleanOp::Difference,   EdgeSide::Inside)  |
+            (BooleanOp::Xor,          EdgeSide::Inside)  => {
+                Some((edge.1, edge.0))
+            },
+            _ => None,
+        };
+        selected.extend(keep);
+    }
+
+    chain_edges(&selected)
+}
+
+
+/// The position of a piece of one polygon's boundary relative to another
+/// polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EdgeSide {
+    /// The edge lies inside of the other polygon.
+    Inside,
+    /// The edge lies outside of the other polygon.
+    Outside,
+    /// The edge lies along an edge of the other polygon in the same direction.
+    Same,
+    /// The edge lies along an edge of the other polygon in the opposite
+    /// direction.
+    Opposite,
+}
+
+/// Returns the given polygon ordered clockwise. Polygons without area are
+/// returned empty.
+fn clockwise(points: &[Point]) -> Vec<Point> {
+    match orientation(points) {
+        Orientation::Clockwise        => points.to_vec(),
+        Orientation::Counterclockwise => points.iter().rev().cloned().collect(),
+        Orientation::Degenerate       => Vec::new(),
+    }
+}
+
+/// Returns the edges of the given polygons, split wherever they meet an edge of
+/// the other polygon. Edges lying along each other are split into identical
+/// pieces.
+fn split_edges(a: &[Point], b: &[Point])
+    -> (Vec<Edge>, Vec<Edge>)
+{
+    // Intersection parameters within this distance of an edge's ends are
+    // snapped to its endpoints, so that vertices lying on the other polygon's
+    // edges are not duplicated.
+    const SNAP: f32 = 1e-6;
+    #[inline]
+    fn snap(t: f32) -> f32 {
+        if t.abs() <= SNAP {
+            0.0
+        } else if (t - 1.0).abs() <= SNAP {
+            1.0
+        } else {
+            t
+        }
+    }
+
+    let a_edges: Vec<_> = edges(a).filter(|&(p, q)| p != q).collect();
+    let b_edges: Vec<_> = edges(b).filter(|&(p, q)| p != q).collect();
+    let mut a_splits = vec![Vec::new(); a_edges.len()];
+    let mut b_splits = vec![Vec::new(); b_edges.len()];
+
+    for (i, &(p0, p1)) in a_edges.iter().enumerate() {
+        for (j, &(q0, q1)) in b_edges.iter().enumerate() {
+            let (r, s) = (p1 - p0, q1 - q0);
+            let denom = r.cross(s);
+            let offset = q0 - p0;
+
+            if denom != 0.0 {
+                // Solve p0 + r * t = q0 + s * u for crossing edges.
+                let t = snap(offset.cross(s) / denom);
+                let u = snap(offset.cross(r) / denom);
+                let unit = 0.0..=1.0;
+                if !unit.contains(&t) || !unit.contains(&u) { continue; }
+
+                let pt = if t == 0.0 { p0 }
+                    else if t == 1.0 { p1 }
+                    else if u == 0.0 { q0 }
+                    else if u == 1.0 { q1 }
+                    else { p0 + r * t };
+                a_splits[i].push((t, pt));
+                b_splits[j].push((u, pt));
+
+            } else if offset.cross(r) == 0.0 {
+                // Split colinear edges at each other's endpoints.
+                for &pt in &[q0, q1] {
+                    let t = (pt - p0).dot(r) / r.length_squared();
+                    if t > 0.0 && t < 1.0 { a_splits[i].push((t, pt)); }
+                }
+                for &pt in &[p0, p1] {
+                    let u = (pt - q0).dot(s) / s.length_squared();
+                    if u > 0.0 && u < 1.0 { b_splits[j].push((u, pt)); }
+                }
+            }
+        }
+    }
+
+    (split_pieces(&a_edges, a_splits), split_pieces(&b_edges, b_splits))
+}
+
+/// Returns the pieces of the given edges between the given split points.
+fn split_pieces(edges: &[Edge], splits: Vec<Vec<(f32, Point)>>)
+    -> Vec<Edge>
+{
+    let mut pieces = Vec::new();
+    for (&(start, end), mut points) in edges.iter().zip(splits) {
+        points.push((0.0, start));
+        points.push((1.0, end));
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).expect("invalid split"));
+        points.dedup_by(|a, b| a.1 == b.1);
+        pieces.extend(points.windows(2).map(|w| (w[0].1, w[1].1)));
+    }
+    pieces
+}
+
+/// Returns the position of the given edge relative to the polygon with the
+/// given vertices and split edges.
+fn classify(
+    edge: Edge,
+    other_edges: &[Edge],
+    other: &[Point])
+    -> EdgeSide
+{
+    if other_edges.contains(&edge) {
+        EdgeSide::Same
+    } else if other_edges.contains(&(edge.1, edge.0)) {
+        EdgeSide::Opposite
+    } else if contains(other, edge.0.lerp(edge.1, 0.5), FillRule::NonZero) {
+        EdgeSide::Inside
+    } else {
+        EdgeSide::Outside
+    }
+}
+
+/// Joins the given directed edges end to start into closed contours.
+fn chain_edges(edges: &[Edge]) -> Vec<Vec<Point>> {
+    let mut used = vec![false; edges.len()];
+    let mut contours = Vec::new();
+    for i in 0..edges.len() {
+        if used[i] { continue; }
+        used[i] = true;
+
+        let (start, mut end) = edges[i];
+        let mut contour = vec![start];
+        while end != start {
+            let next = (0..edges.len())
+                .find(|&j| !used[j] && edges[j].0 == end);
+            match next {
+                Some(j) => {
+                    used[j] = true;
+                    contour.push(end);
+                    end = edges[j].1;
+                },
+                None    => break,
+            }
+        }
+
+        let contour = without_colinear(contour);
+        if contour.len() >= 3 { contours.push(contour); }
+    }
+    contours
+}
+
+/// Returns the given polygon without the vertices lying along a straight edge.
+fn without_colinear(mut points: Vec<Point>) -> Vec<Point> {
+    // Removing a vertex may leave its neighbors along a straight edge, so
+    // repeat until no vertices are removed.
+    let mut removed = true;
+    while removed && points.len() >= 3 {
+        removed = false;
+        let n = points.len();
+        for i in 0..n {
+            let prev = points[(i + n - 1) % n];
+            let next = points[(i + 1) % n];
+            let (incoming, outgoing) = (points[i] - prev, next - points[i]);
+            if incoming.cross(outgoing) == 0.0 && incoming.dot(outgoing) > 0.0 {
+                points.remove(i);
+                removed = true;
+                break;
+            }
+        }
+    }
+    points
+}
@@ -0,0 +1,132 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Line segment primitive.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use geometry::Point;
+use utilities::clamped;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Segment
+////////////////////////////////////////////////////////////////////////////////
+/// A line segment between two [`Point`]s.
+///
+/// [`Point`]: struct.Point.html
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    /// Returns a new `Segment` with the given endpoints.
+    #[inline]
+    pub fn new(start: Point, end: Point) -> Self {
+        Segment { start, end }
+    }
+
+    /// Returns the endpoints of the segment.
+    #[inline]
+    pub fn endpoints(&self) -> [Point; 2] {
+        [self.start, self.end]
+    }
+
+    /// Returns `true` if the segment's endpoints are equal.
+    #[inline]
+    pub fn is_degenerate(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns the length of the segment.
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.start.distance(self.end)
+    }
+
+    /// Returns the point halfway between the segment's endpoints.
+    #[inline]
+    pub fn midpoint(&self) -> Point {
+        self.point_at(0.5)
+    }
+
+    /// Returns the unit vector pointing from the start of the segment to its
+    /// end, or the zero vector if the segment is degenerate.
+    #[inline]
+    pub fn direction(&self) -> Point {
+        (self.end - self.start).normalized()
+    }
+
+    /// Returns the unit vector perpendicular to the segment, or the zero vector
+    /// if the segment is degenerate. The normal is the segment's direction
+    /// turned a quarter turn clockwise, as seen with the y-axis pointing down.
+    #[inline]
+    pub fn normal(&self) -> Point {
+        self.direction().perpendicular()
+    }
+
+    /// Returns the point at the given parameter along the segment, where `0.0`
+    /// is the start of the segment and `1.0` is its end. Parameters outside of
+    /// that range give points on the extension of the segment.
+    #[inline]
+    pub fn point_at(&self, t: f32) -> Point {
+        self.start + (self.end - self.start) * t
+    }
+
+    /// Returns the point on the segment nearest to the given point.
+    pub fn closest_point(&self, pt: Point) -> Point {
+        let span = self.end - self.start;
+        let length_squared = span.length_squared();
+        if length_squared == 0.0 { return self.start; }
+
+        // Project the point onto the segment's line, then keep the projection
+        // within the endpoints.
+        let t = (pt - self.start).dot(span) / length_squared;
+        self.point_at(clamped(t, 0.0, 1.0))
+    }
+
+    /// Returns the distance from the given point to the nearest point on the
+    /// segment.
+    #[inline]
+    pub fn distance_to(&self, pt: Point) -> f32 {
+        self.closest_point(pt).distance(pt)
+    }
+
+    /// Returns the segment with its endpoints swapped.
+    #[inline]
+    pub fn reversed(&self) -> Segment {
+        Segment { start: self.end, end: self.start }
+    }
+}
+
+// Conversion traits
+
+impl From<[Point; 2]> for Segment {
+    #[inline]
+    fn from(endpoints: [Point; 2]) -> Self {
+        Segment { start: endpoints[0], end: endpoints[1] }
+    }
+}
+
+impl From<(Point, Point)> for Segment {
+    #[inline]
+    fn from(endpoints: (Point, Point)) -> Self {
+        Segment { start: endpoints.0, end: endpoints.1 }
+    }
+}
+
+impl From<Segment> for [Point; 2] {
+    #[inline]
+    fn from(segment: Segment) -> Self {
+        [segment.start, segment.end]
+    }
+}
@@ -20,6 +20,7 @@
 ////////////////////////////////////////////////////////////////////////////////
 #![feature(int_to_from_bytes)]
 
+extern crate owned_ttf_parser;
 extern crate rusttype;
 
 // Public modules.
@@ -41,15 +42,51 @@ mod test;
 
 // Exports.
 pub use brush::Brush;
+pub use brush::DarkenBrush;
+pub use brush::EraserBrush;
+pub use brush::LightenBrush;
+pub use brush::RoundBrush;
+pub use brush::SoftBrush;
+pub use brush::SquareBrush;
+pub use brush::StampBrush;
+pub use brush::StrokePoint;
+pub use brush::StrokeStyle;
+pub use brush::XorBrush;
 pub use canvas::Canvas;
+pub use geometry::IRect;
+pub use geometry::Path;
 pub use geometry::Point;
 pub use geometry::Position;
 pub use geometry::Rect;
 pub use geometry::Scale;
+pub use geometry::Segment;
 pub use pattern::Pattern;
+pub use primitive::BitmapFont;
+pub use primitive::DecorationMetrics;
 pub use primitive::Font;
+pub use primitive::FontSet;
 pub use primitive::FontStyle;
+pub use primitive::GlyphCache;
+pub use primitive::HorizontalAnchor;
+pub use primitive::LineMetrics;
+pub use primitive::PreparedRichText;
 pub use primitive::PreparedText;
+pub use primitive::SubpixelOrder;
+pub use primitive::TextAlign;
+pub use primitive::TextAnchor;
+pub use primitive::TextDirection;
+pub use primitive::TextEffects;
+pub use primitive::TextLayout;
+pub use primitive::TextOptions;
+pub use primitive::TextOutline;
+pub use primitive::TextPath;
+pub use primitive::TextShadow;
+pub use primitive::TextSpan;
+pub use primitive::TextWrap;
+pub use primitive::TruncateMode;
+pub use primitive::Typeface;
+pub use primitive::VerticalAnchor;
+pub use primitive::VMetrics;
 
 
 
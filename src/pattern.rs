@@ -11,6 +11,7 @@
 
 // Local imports.
 use canvas::Canvas;
+use geometry::IRect;
 use geometry::Point;
 use geometry::Rect;
 use utilities::lerp;
@@ -25,13 +26,30 @@ pub trait Pattern<X> {
         where
             C: Canvas<Pixel=X>;
 
-    /// Applies the pattern to the given canvas.
-    // mask = opacity float
+    /// Applies the pattern to each pixel of the canvas overlapped by the given
+    /// [`Rect`], as determined by its [`IRect`]. The `mask` function is sampled
+    /// at the center of each pixel and returns the opacity with which the
+    /// pattern is applied there.
+    ///
+    /// [`Rect`]: geometry/struct.Rect.html
+    /// [`IRect`]: geometry/struct.IRect.html
     fn paint<C, M>(&self, canvas: &mut C, rect: Rect, mask: M)
         where
             C: Canvas<Pixel=X>,
             M: Fn(Point) -> f32;
 
+    /// Applies the pattern to the pixel at the given point with separate
+    /// opacities for its red, green, and blue subpixels. By default, the
+    /// pattern is applied with the average of the opacities.
+    #[inline]
+    fn apply_subpixel<C>(&self, canvas: &mut C, pt: Point, opacity: [f32; 3])
+        where
+            C: Canvas<Pixel=X>
+    {
+        let average = (opacity[0] + opacity[1] + opacity[2]) / 3.0;
+        self.apply(canvas, pt, average);
+    }
+
     /// Returns the size of the pattern.
     #[inline]
     fn size(&self) -> (u32, u32) { 
@@ -62,29 +80,53 @@ impl Pattern<u32> for u32 {
         where
             C: Canvas<Pixel=u32>
     {
-        canvas.aligned_pixel_mut(pt)
-            .map(|p| {
-                // RGBA blend.
-                let bg = p.to_bytes();
-                let fg = self.to_bytes();
-                let blend: [u8; 4] = [
-                    lerp(bg[0] as f32, fg[0] as f32, opacity) as u8,
-                    lerp(bg[1] as f32, fg[1] as f32, opacity) as u8,
-                    lerp(bg[2] as f32, fg[2] as f32, opacity) as u8,
-                    0
-                ];
-                *p = u32::from_bytes(blend);
-            });
+        if let Some(p) = canvas.aligned_pixel_mut(pt) {
+            *p = blend_channels(*p, *self, [opacity; 3], 0);
+        }
     }
 
-    fn paint<C, M>(&self, _canvas: &mut C, _rect: Rect, _mask: M)
+    fn apply_subpixel<C>(&self, canvas: &mut C, pt: Point, opacity: [f32; 3])
+        where
+            C: Canvas<Pixel=u32>
+    {
+        if let Some(p) = canvas.aligned_pixel_mut(pt) {
+            *p = blend_channels(*p, *self, opacity, 0);
+        }
+    }
+
+    fn paint<C, M>(&self, canvas: &mut C, rect: Rect, mask: M)
         where
             C: Canvas<Pixel=u32>,
             M: Fn(Point) -> f32
     {
-        unimplemented!()
-        // canvas.each_pixel_mut(|pt, pix| if (mask)(pt) { *pix = *self });
+        // Restrict the painted area to the pixels covered by the canvas.
+        let rect = match rect.intersection(&canvas.bounding_rect()) {
+            Some(rect) => rect,
+            None       => return,
+        };
+
+        for pos in IRect::from(rect) {
+            let (x, y) = (pos.x as f32, pos.y as f32);
+            // Sample the mask at the pixel center.
+            let coverage = (mask)(Point { x: x + 0.5, y: y + 0.5 });
+            if coverage > 0.0 {
+                self.apply(canvas, Point { x, y }, coverage);
+            }
+        }
     }
 }
 
 
+/// Blends the color channels of two RGBA pixels with a separate opacity for
+/// each channel, giving the result the given alpha channel.
+#[inline]
+pub fn blend_channels(bg: u32, fg: u32, opacity: [f32; 3], alpha: u8) -> u32 {
+    let bg = bg.to_bytes();
+    let fg = fg.to_bytes();
+    u32::from_bytes([
+        lerp(bg[0] as f32, fg[0] as f32, opacity[0]) as u8,
+        lerp(bg[1] as f32, fg[1] as f32, opacity[1]) as u8,
+        lerp(bg[2] as f32, fg[2] as f32, opacity[2]) as u8,
+        alpha,
+    ])
+}
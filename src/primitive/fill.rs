@@ -0,0 +1,91 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Fill drawing primitives.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use canvas::Canvas;
+use geometry::Path;
+use geometry::Point;
+use geometry::Rect;
+use pattern::Pattern;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// fill_masked
+////////////////////////////////////////////////////////////////////////////////
+/// Fills a region with a pattern, weighted by a coverage mask.
+///
+/// The filled region will be cropped within the boundaries of the canvas.
+///
+/// # Arguments
+///
+/// `canvas`: The [`Canvas`] to draw to.
+///
+/// `pattern`: The [`Pattern`] to fill with.
+///
+/// `rect`: The [`Rect`] bounding the filled region.
+///
+/// `mask`: A function returning the coverage of the region at a given
+/// [`Point`]. Coverage is used as the opacity of the pattern, so values at or
+/// below `0.0` are left unpainted.
+///
+/// [`Canvas`]: ../canvas/trait.Canvas.html
+/// [`Pattern`]: ../pattern/trait.Pattern.html
+/// [`Rect`]: ../geometry/struct.Rect.html
+/// [`Point`]: ../geometry/struct.Point.html
+#[inline]
+pub fn fill_masked<C, P, M, X>(
+    canvas: &mut C,
+    pattern: &P,
+    rect: Rect,
+    mask: M)
+    where
+        C: Canvas<Pixel=X>,
+        P: Pattern<X>,
+        M: Fn(Point) -> f32
+{
+    pattern.paint(canvas, rect, mask);
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// fill_path
+////////////////////////////////////////////////////////////////////////////////
+/// Fills the interior of a path with a pattern.
+///
+/// Every contour of the path is treated as closed, and the interior is
+/// determined by the nonzero winding rule. Edges are antialiased.
+///
+/// # Arguments
+///
+/// `canvas`: The [`Canvas`] to draw to.
+///
+/// `pattern`: The [`Pattern`] to fill with.
+///
+/// `path`: The [`Path`] to fill.
+///
+/// [`Canvas`]: ../canvas/trait.Canvas.html
+/// [`Pattern`]: ../pattern/trait.Pattern.html
+/// [`Path`]: ../geometry/struct.Path.html
+pub fn fill_path<C, P, X>(
+    canvas: &mut C,
+    pattern: &P,
+    path: &Path)
+    where
+        C: Canvas<Pixel=X>,
+        P: Pattern<X>,
+{
+    // Only the pixels covered by the canvas are sampled.
+    let rect = path.bounds()
+        .and_then(|bounds| bounds.intersection(&canvas.bounding_rect()));
+    if let Some(rect) = rect {
+        pattern.paint(canvas, rect, path.mask(rect));
+    }
+}
@@ -13,6 +13,7 @@
 use brush::Brush;
 use canvas::Canvas;
 use geometry::Point;
+use geometry::Segment;
 use geometry::clip_segment_to_rect;
 use utilities::clipped;
 use utilities::ordered;
@@ -31,25 +32,28 @@ use utilities::ordered;
 ///
 /// `brush`: The [`Brush`] to draw with.
 ///
-/// `endpoints`: The [`Point`]s of the line segment's endpoints.
+/// `endpoints`: The line [`Segment`].
 ///
 /// [`Canvas`]: ../canvas/trait.Canvas.html
 /// [`Brush`]: ../brush/trait.Brush.html
-/// [`Point`]: ../geometry/struct.Point.html
-pub fn segment<C, B, X>(
+/// [`Segment`]: ../geometry/struct.Segment.html
+pub fn segment<C, B, S, X>(
     canvas: &mut C,
     brush: &B,
-    endpoints: [Point; 2])
+    endpoints: S)
     where
         C: Canvas<Pixel=X>,
-        B: Brush<X>
+        B: Brush<X>,
+        S: Into<Segment>,
 {
+    let endpoints = endpoints.into();
     let rect = canvas.virtual_bounding_rect(brush);
     // println!(" :segment ends {:?}", endpoints);
     // println!("          rect {:?}", rect);
     // println!("          clip {:?}", clip_segment_to_rect(endpoints, rect));
 
     if let Some(segment) = clip_segment_to_rect(endpoints, rect) {
+        let segment = segment.endpoints();
         let [Point { x: xa, y: ya }, Point { x: xb, y: yb }] = segment;
         
         if (yb - ya).abs() < (xb - xa).abs() {
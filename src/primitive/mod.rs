@@ -10,11 +10,15 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 // Internal modules.
+mod fill;
 mod line;
 mod point;
+mod stroke;
 mod text;
 
 // Exports.
+pub use self::fill::fill_masked;
+pub use self::fill::fill_path;
 pub use self::line::line;
 pub use self::line::line_horizontal;
 pub use self::line::line_vertical;
@@ -24,10 +28,39 @@ pub use self::line::segment_extended;
 pub use self::line::segment_horizontal;
 pub use self::line::segment_vertical;
 pub use self::point::point;
+pub use self::stroke::stroke;
+pub use self::stroke::stroke_path;
+pub use self::text::BitmapFont;
+pub use self::text::DecorationMetrics;
 pub use self::text::Font;
+pub use self::text::FontSet;
 pub use self::text::FontStyle;
 pub use self::text::glyph;
+pub use self::text::GlyphCache;
+pub use self::text::HorizontalAnchor;
+pub use self::text::LineMetrics;
 pub use self::text::prepare_glyph;
 pub use self::text::prepare_text;
+pub use self::text::prepare_text_fitted;
+pub use self::text::prepare_rich_text;
+pub use self::text::prepare_text_layout;
+pub use self::text::PreparedRichText;
 pub use self::text::PreparedText;
+pub use self::text::rich_text;
 pub use self::text::text;
+pub use self::text::SubpixelOrder;
+pub use self::text::TextAlign;
+pub use self::text::TextAnchor;
+pub use self::text::TextDirection;
+pub use self::text::TextEffects;
+pub use self::text::TextLayout;
+pub use self::text::TextOptions;
+pub use self::text::TextOutline;
+pub use self::text::TextPath;
+pub use self::text::TextShadow;
+pub use self::text::TextSpan;
+pub use self::text::TextWrap;
+pub use self::text::TruncateMode;
+pub use self::text::Typeface;
+pub use self::text::VerticalAnchor;
+pub use self::text::VMetrics;
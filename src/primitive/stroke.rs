@@ -0,0 +1,150 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Brush stroke primitives.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use brush::Brush;
+use brush::StrokePoint;
+use brush::StrokeStyle;
+use canvas::Canvas;
+use geometry::Path;
+use geometry::Point;
+use utilities::lerp;
+
+
+/// The minimum distance between stamps, used to guard against degenerate
+/// spacings.
+const MIN_STAMP_SPACING: f32 = 0.1;
+
+/// The maximum distance between a curve and the polyline approximating it when
+/// stroking a path.
+const PATH_TOLERANCE: f32 = 0.25;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// stroke
+////////////////////////////////////////////////////////////////////////////////
+/// Draws a brush stroke by placing evenly spaced brush stamps along a polyline.
+///
+/// The first stamp is placed on the first vertex. Each following stamp is
+/// placed along the polyline at a distance from the previous stamp given by
+/// the `StrokeStyle`'s spacing multiplied by the size of the previous stamp.
+/// Any distance left over at the end of a segment is carried over into the
+/// next one, so spacing is even across vertices. The pressure, and with it the
+/// size and opacity of each stamp, is interpolated between vertices.
+///
+/// # Arguments
+///
+/// `canvas`: The [`Canvas`] to draw to.
+///
+/// `brush`: The [`Brush`] to draw with.
+///
+/// `style`: The [`StrokeStyle`] describing the stamp placement.
+///
+/// `vertices`: The [`StrokePoint`]s (or [`Point`]s) of the polyline.
+///
+/// [`Canvas`]: ../canvas/trait.Canvas.html
+/// [`Brush`]: ../brush/trait.Brush.html
+/// [`StrokeStyle`]: ../brush/struct.StrokeStyle.html
+/// [`StrokePoint`]: ../brush/struct.StrokePoint.html
+/// [`Point`]: ../geometry/struct.Point.html
+pub fn stroke<C, B, X, I>(
+    canvas: &mut C,
+    brush: &B,
+    style: StrokeStyle,
+    vertices: I)
+    where
+        C: Canvas<Pixel=X>,
+        B: Brush<X> + ?Sized,
+        I: IntoIterator,
+        I::Item: Into<StrokePoint>,
+{
+    let (w, h) = brush.size();
+    let brush_size = w.max(h) as f32;
+
+    // Returns the distance to the next stamp after one at the given pressure.
+    let spacing = |pressure: f32| {
+        (style.spacing * brush_size * style.size_at(pressure))
+            .max(MIN_STAMP_SPACING)
+    };
+
+    let mut vertices = vertices.into_iter().map(Into::into);
+    let mut prev: StrokePoint = match vertices.next() {
+        Some(first) => first,
+        None        => return,
+    };
+
+    brush.apply_stamp(
+        canvas,
+        prev.pt,
+        style.size_at(prev.pressure),
+        style.opacity_at(prev.pressure));
+    let mut remaining = spacing(prev.pressure);
+
+    for next in vertices {
+        let d = next.pt - prev.pt;
+        let len = (d.x * d.x + d.y * d.y).sqrt();
+
+        // Place stamps along the segment, starting from the distance left over
+        // from the previous segment.
+        let mut pos = 0.0;
+        while remaining <= len - pos {
+            pos += remaining;
+            let t = pos / len;
+            let pressure = lerp(prev.pressure, next.pressure, t);
+            brush.apply_stamp(
+                canvas,
+                Point { x: prev.pt.x + d.x * t, y: prev.pt.y + d.y * t },
+                style.size_at(pressure),
+                style.opacity_at(pressure));
+            remaining = spacing(pressure);
+        }
+        remaining -= len - pos;
+        prev = next;
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// stroke_path
+////////////////////////////////////////////////////////////////////////////////
+/// Draws a brush stroke along each contour of a path.
+///
+/// Curves are approximated by polylines, and each contour is drawn as a
+/// separate [`stroke`] at full pressure.
+///
+/// # Arguments
+///
+/// `canvas`: The [`Canvas`] to draw to.
+///
+/// `brush`: The [`Brush`] to draw with.
+///
+/// `style`: The [`StrokeStyle`] describing the stamp placement.
+///
+/// `path`: The [`Path`] to stroke.
+///
+/// [`stroke`]: fn.stroke.html
+/// [`Canvas`]: ../canvas/trait.Canvas.html
+/// [`Brush`]: ../brush/trait.Brush.html
+/// [`StrokeStyle`]: ../brush/struct.StrokeStyle.html
+/// [`Path`]: ../geometry/struct.Path.html
+pub fn stroke_path<C, B, X>(
+    canvas: &mut C,
+    brush: &B,
+    style: StrokeStyle,
+    path: &Path)
+    where
+        C: Canvas<Pixel=X>,
+        B: Brush<X> + ?Sized,
+{
+    for contour in path.flattened(PATH_TOLERANCE) {
+        stroke(canvas, brush, style, contour);
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Text anchoring.
+////////////////////////////////////////////////////////////////////////////////
+
+
+////////////////////////////////////////////////////////////////////////////////
+// HorizontalAnchor
+////////////////////////////////////////////////////////////////////////////////
+/// The horizontal position within a text's advance width which is placed at
+/// the drawn point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HorizontalAnchor {
+    /// The left edge of the text.
+    Left,
+    /// The horizontal center of the text.
+    Center,
+    /// The right edge of the text.
+    Right,
+}
+
+impl Default for HorizontalAnchor {
+    #[inline]
+    fn default() -> Self {
+        HorizontalAnchor::Left
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// VerticalAnchor
+////////////////////////////////////////////////////////////////////////////////
+/// The vertical position within a text's line heights which is placed at the
+/// drawn point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VerticalAnchor {
+    /// The top of the first line of the text.
+    Top,
+    /// The vertical center of the text.
+    Middle,
+    /// The baseline of the first line of the text.
+    Baseline,
+    /// The bottom of the last line of the text.
+    Bottom,
+}
+
+impl Default for VerticalAnchor {
+    #[inline]
+    fn default() -> Self {
+        VerticalAnchor::Top
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TextAnchor
+////////////////////////////////////////////////////////////////////////////////
+/// The position within a text which is placed at the drawn point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TextAnchor {
+    pub horizontal: HorizontalAnchor,
+    pub vertical: VerticalAnchor,
+}
+
+impl TextAnchor {
+    /// Returns a new `TextAnchor` with the given horizontal and vertical
+    /// anchors.
+    #[inline]
+    pub fn new(horizontal: HorizontalAnchor, vertical: VerticalAnchor) -> Self {
+        TextAnchor { horizontal, vertical }
+    }
+
+    /// Returns a `TextAnchor` placing the top-left of the text at the drawn
+    /// point.
+    #[inline]
+    pub fn top_left() -> Self {
+        TextAnchor::default()
+    }
+
+    /// Returns a `TextAnchor` placing the center of the text at the drawn
+    /// point.
+    #[inline]
+    pub fn center() -> Self {
+        TextAnchor::new(HorizontalAnchor::Center, VerticalAnchor::Middle)
+    }
+
+    /// Returns a `TextAnchor` placing the left end of the first baseline of
+    /// the text at the drawn point.
+    #[inline]
+    pub fn baseline_left() -> Self {
+        TextAnchor::new(HorizontalAnchor::Left, VerticalAnchor::Baseline)
+    }
+}
@@ -0,0 +1,264 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Bitmap fonts.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use geometry::Path;
+use geometry::Point;
+use geometry::Position;
+use geometry::Rect;
+use geometry::Scale;
+use super::typeface::Typeface;
+use super::typeface::VMetrics;
+
+// Standard library imports.
+use std::collections::HashMap;
+
+
+/// The glyph id used for characters missing from a `BitmapFont`.
+const MISSING_GLYPH: u32 = u32::MAX;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// BitmapFont
+////////////////////////////////////////////////////////////////////////////////
+/// A fixed-cell bitmap font.
+///
+/// Each glyph is a grid of opaque or transparent pixels the size of the font's
+/// cell, and every glyph advances by the cell width. Glyphs are drawn without
+/// antialiasing, scaled by the whole number multiple of the cell height nearest
+/// to the requested scale. Characters missing from the font advance by the
+/// cell width without drawing anything. The outline of a glyph is made of a
+/// square contour around each of its pixels.
+#[derive(Debug, Clone)]
+pub struct BitmapFont {
+    cell_width: u32,
+    cell_height: u32,
+    baseline: u32,
+    /// The row-major pixels of each glyph.
+    glyphs: Vec<Vec<bool>>,
+    /// The glyph id of each character.
+    characters: HashMap<char, u32>,
+}
+
+impl BitmapFont {
+    /// Returns a new `BitmapFont` with no glyphs. The baseline is given as the
+    /// number of rows of the cell lying above it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either cell dimension is zero or the baseline lies outside of
+    /// the cell.
+    pub fn new(cell_width: u32, cell_height: u32, baseline: u32) -> Self {
+        assert!(cell_width > 0 && cell_height > 0, "empty bitmap font cell");
+        assert!(baseline <= cell_height,
+            "baseline outside of bitmap font cell");
+        BitmapFont {
+            cell_width,
+            cell_height,
+            baseline,
+            glyphs: Vec::new(),
+            characters: HashMap::new(),
+        }
+    }
+
+    /// Returns a new `BitmapFont` read from a glyph sheet.
+    ///
+    /// The sheet is given as the row-major pixels of an image with the given
+    /// width, divided into cells which are assigned to the given characters in
+    /// order, left to right and top to bottom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sheet has fewer cells than there are characters, or if
+    /// `BitmapFont::new` would panic.
+    pub fn from_sheet(
+        sheet: &[bool],
+        sheet_width: usize,
+        cell_width: u32,
+        cell_height: u32,
+        baseline: u32,
+        characters: &str)
+        -> Self
+    {
+        let mut font = BitmapFont::new(cell_width, cell_height, baseline);
+        let (cw, ch) = (cell_width as usize, cell_height as usize);
+        let columns = (sheet_width / cw).max(1);
+        let rows = sheet.len() / sheet_width.max(1) / ch;
+
+        for (i, character) in characters.chars().enumerate() {
+            let (column, row) = (i % columns, i / columns);
+            assert!(
+                row < rows && (column + 1) * cw <= sheet_width,
+                "bitmap font sheet too small");
+
+            let mut pixels = Vec::with_capacity(cw * ch);
+            for y in 0..ch {
+                let start = (row * ch + y) * sheet_width + column * cw;
+                pixels.extend_from_slice(&sheet[start..start + cw]);
+            }
+            font.insert(character, pixels);
+        }
+        font
+    }
+
+    /// Adds a glyph for the given character to the font, replacing any
+    /// existing glyph. The pixels are given in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of pixels does not match the cell size.
+    pub fn insert(&mut self, character: char, pixels: Vec<bool>) {
+        assert_eq!(
+            pixels.len(),
+            (self.cell_width * self.cell_height) as usize,
+            "bitmap glyph does not match the cell size");
+
+        match self.characters.get(&character) {
+            Some(&id) => self.glyphs[id as usize] = pixels,
+            None      => {
+                self.characters.insert(character, self.glyphs.len() as u32);
+                self.glyphs.push(pixels);
+            },
+        }
+    }
+
+    /// Returns the width and height of the font's cells.
+    #[inline]
+    pub fn cell_size(&self) -> (u32, u32) {
+        (self.cell_width, self.cell_height)
+    }
+
+    /// Returns the number of rows of the cell lying above the baseline.
+    #[inline]
+    pub fn baseline(&self) -> u32 {
+        self.baseline
+    }
+
+    /// Returns the horizontal and vertical pixel multiples used to draw the
+    /// font at the given scale.
+    #[inline]
+    fn multiples(&self, scale: Scale) -> (i32, i32) {
+        let height = self.cell_height as f32;
+        (
+            (scale.horz / height).round().max(1.0) as i32,
+            (scale.vert / height).round().max(1.0) as i32,
+        )
+    }
+
+    /// Returns the positions of the opaque pixels of the given glyph within its
+    /// cell.
+    fn opaque_pixels<'a>(&'a self, glyph: u32)
+        -> impl Iterator<Item=(i32, i32)> + 'a
+    {
+        let width = self.cell_width as usize;
+        self.glyphs.get(glyph as usize)
+            .into_iter()
+            .flat_map(move |pixels| pixels.iter()
+                .enumerate()
+                .filter(|&(_, &opaque)| opaque)
+                .map(move |(i, _)| ((i % width) as i32, (i / width) as i32)))
+    }
+}
+
+impl Typeface for BitmapFont {
+    #[inline]
+    fn glyph_id(&self, character: char) -> u32 {
+        self.characters.get(&character).cloned().unwrap_or(MISSING_GLYPH)
+    }
+
+    #[inline]
+    fn has_glyph(&self, character: char) -> bool {
+        self.characters.contains_key(&character)
+    }
+
+    #[inline]
+    fn v_metrics(&self, scale: Scale) -> VMetrics {
+        let (_, my) = self.multiples(scale);
+        VMetrics {
+            ascent: (self.baseline as i32 * my) as f32,
+            descent: -((self.cell_height - self.baseline) as i32 * my) as f32,
+            line_gap: 0.0,
+        }
+    }
+
+    #[inline]
+    fn advance(&self, _glyph: u32, scale: Scale) -> f32 {
+        let (mx, _) = self.multiples(scale);
+        (self.cell_width as i32 * mx) as f32
+    }
+
+    fn bounds(&self, glyph: u32, scale: Scale) -> Option<Rect> {
+        let (mx, my) = self.multiples(scale);
+        let baseline = self.baseline as i32;
+        self.opaque_pixels(glyph)
+            .map(|(x, y)| Rect {
+                left: (x * mx) as f32,
+                top: ((y - baseline) * my) as f32,
+                right: ((x + 1) * mx) as f32,
+                bottom: ((y + 1 - baseline) * my) as f32,
+            })
+            .fold(None, |acc: Option<Rect>, r| Some(match acc {
+                Some(acc) => Rect {
+                    left: acc.left.min(r.left),
+                    top: acc.top.min(r.top),
+                    right: acc.right.max(r.right),
+                    bottom: acc.bottom.max(r.bottom),
+                },
+                None      => r,
+            }))
+    }
+
+    #[inline]
+    fn scale_for_pixel_height(&self, height: f32) -> f32 {
+        (height / self.cell_height as f32).round().max(1.0)
+    }
+
+    fn outline(&self, glyph: u32, scale: Scale) -> Option<Path> {
+        let (mx, my) = self.multiples(scale);
+        let baseline = self.baseline as i32;
+        let mut path = Path::new();
+        for (x, y) in self.opaque_pixels(glyph) {
+            let (left, top) = ((x * mx) as f32, ((y - baseline) * my) as f32);
+            let (right, bottom) = (left + mx as f32, top + my as f32);
+            path.move_to(Point { x: left, y: top });
+            path.line_to(Point { x: right, y: top });
+            path.line_to(Point { x: right, y: bottom });
+            path.line_to(Point { x: left, y: bottom });
+            path.close();
+        }
+
+        if path.is_empty() { None } else { Some(path) }
+    }
+
+    fn rasterize(
+        &self,
+        glyph: u32,
+        scale: Scale,
+        origin: Point,
+        coverage: &mut dyn FnMut(Position, f32))
+    {
+        let (mx, my) = self.multiples(scale);
+        // Snap the cell to the pixel grid to keep the glyph crisp.
+        let left = origin.x.round() as i32;
+        let top = origin.y.round() as i32 - self.baseline as i32 * my;
+
+        for (x, y) in self.opaque_pixels(glyph) {
+            for dy in 0..my {
+                for dx in 0..mx {
+                    coverage(Position {
+                        x: left + x * mx + dx,
+                        y: top + y * my + dy,
+                    }, 1.0);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,264 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Glyph rasterization cache.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use canvas::Canvas;
+use geometry::Point;
+use pattern::Pattern;
+use super::OffsetGlyph;
+use super::typeface::Coverage;
+use super::typeface::face_id;
+use super::typeface::Typeface;
+
+// Standard library imports.
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+
+/// The number of subpixel positions cached for each glyph along each axis.
+const SUBPIXEL_STEPS: f32 = 4.0;
+
+/// The minimum number of bytes charged against the cache's capacity for each
+/// bitmap, so that empty glyphs can not accumulate without bound.
+const MIN_BITMAP_SIZE: usize = 1;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// GlyphKey
+////////////////////////////////////////////////////////////////////////////////
+/// Identifies a rasterized glyph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct GlyphKey {
+    /// The address of the glyph's typeface. The cache borrows its typefaces,
+    /// so the address can not be reused by another typeface.
+    face: usize,
+    glyph: u32,
+    /// The bit patterns of the horizontal and vertical scale.
+    scale: (u32, u32),
+    /// The quantized subpixel offset of the glyph origin.
+    subpixel: (u8, u8),
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// GlyphBitmap
+////////////////////////////////////////////////////////////////////////////////
+/// A rasterized glyph's coverage.
+#[derive(Debug, Clone)]
+struct GlyphBitmap {
+    /// The offset of the bitmap's top-left from the glyph's pixel origin.
+    left: i32,
+    top: i32,
+    width: usize,
+    /// The row-major coverage values of the bitmap.
+    coverage: Vec<u8>,
+    /// The number of bytes the bitmap is charged against the cache capacity.
+    size: usize,
+    /// The tick of the most recent use of the bitmap.
+    last_used: u64,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// GlyphCache
+////////////////////////////////////////////////////////////////////////////////
+/// A cache of rasterized glyph coverage bitmaps.
+///
+/// Glyphs are cached by typeface, glyph, scale, and subpixel position.
+/// Typefaces are identified by their address, and the cache borrows the
+/// typefaces it is used with, so no typeface can be dropped and replaced at the
+/// same address while the cache is alive. When the total size of the cached
+/// bitmaps would exceed the cache's capacity, the least recently used bitmaps
+/// are evicted. Empty glyphs, such as spaces, are cached without any coverage
+/// data, but are still charged a minimum size against the capacity.
+#[derive(Debug, Clone)]
+pub struct GlyphCache<'f> {
+    capacity: usize,
+    size: usize,
+    tick: u64,
+    bitmaps: HashMap<GlyphKey, GlyphBitmap>,
+    usage: BTreeMap<u64, GlyphKey>,
+    faces: PhantomData<&'f dyn Typeface>,
+}
+
+impl<'f> GlyphCache<'f> {
+    /// Returns a new `GlyphCache` holding at most the given number of bytes of
+    /// coverage data.
+    #[inline]
+    pub fn new(capacity: usize) -> Self {
+        GlyphCache {
+            capacity,
+            size: 0,
+            tick: 0,
+            bitmaps: HashMap::new(),
+            usage: BTreeMap::new(),
+            faces: PhantomData,
+        }
+    }
+
+    /// Returns the maximum number of bytes of coverage data in the cache.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of bytes of coverage data in the cache, including the
+    /// minimum size charged for each bitmap.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of glyph bitmaps in the cache.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bitmaps.len()
+    }
+
+    /// Returns `true` if the cache contains no glyph bitmaps.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bitmaps.is_empty()
+    }
+
+    /// Removes all glyph bitmaps from the cache.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.size = 0;
+        self.bitmaps.clear();
+        self.usage.clear();
+    }
+
+    /// Draws the given glyph with its origin at the given point, rasterizing
+    /// and caching it if necessary.
+    pub(in primitive::text) fn draw_glyph<C, P, X>(
+        &mut self,
+        canvas: &mut C,
+        pattern: &P,
+        glyph: &OffsetGlyph<'f>,
+        pt: Point)
+        where
+            C: Canvas<Pixel=X>,
+            P: Pattern<X>,
+    {
+        // Round the position to the nearest subpixel step, then split it into
+        // a pixel origin and subpixel offset.
+        let qx = (pt.x * SUBPIXEL_STEPS).round();
+        let qy = (pt.y * SUBPIXEL_STEPS).round();
+        let x = (qx / SUBPIXEL_STEPS).floor();
+        let y = (qy / SUBPIXEL_STEPS).floor();
+        let sx = qx - x * SUBPIXEL_STEPS;
+        let sy = qy - y * SUBPIXEL_STEPS;
+
+        let key = GlyphKey {
+            face: face_id(glyph.face),
+            glyph: glyph.id,
+            scale: (glyph.scale.horz.to_bits(), glyph.scale.vert.to_bits()),
+            subpixel: (sx as u8, sy as u8),
+        };
+
+        self.tick += 1;
+        let tick = self.tick;
+        if let Some(bitmap) = self.bitmaps.get_mut(&key) {
+            self.usage.remove(&bitmap.last_used);
+            self.usage.insert(tick, key);
+            bitmap.last_used = tick;
+            return GlyphCache::blit(canvas, pattern, bitmap, x, y);
+        }
+
+        let bitmap = GlyphCache::rasterize(glyph, sx, sy, tick);
+        GlyphCache::blit(canvas, pattern, &bitmap, x, y);
+
+        // Bitmaps too large to ever fit are not cached.
+        let bitmap_size = bitmap.size;
+        if bitmap_size > self.capacity { return; }
+
+        while self.size + bitmap_size > self.capacity {
+            self.evict_oldest();
+        }
+        self.size += bitmap_size;
+        self.usage.insert(tick, key);
+        self.bitmaps.insert(key, bitmap);
+    }
+
+    /// Removes the least recently used bitmap from the cache.
+    fn evict_oldest(&mut self) {
+        let oldest = self.usage.keys().next().cloned();
+        if let Some(tick) = oldest {
+            let key = self.usage.remove(&tick).expect("usage entry for tick");
+            if let Some(bitmap) = self.bitmaps.remove(&key) {
+                self.size -= bitmap.size;
+            }
+        }
+    }
+
+    /// Rasterizes the given glyph at the given subpixel offset. Glyphs with no
+    /// visible pixels give an empty bitmap.
+    fn rasterize(glyph: &OffsetGlyph, sx: f32, sy: f32, tick: u64)
+        -> GlyphBitmap
+    {
+        let coverage = match Coverage::rasterize(
+            glyph.face,
+            glyph.id,
+            glyph.scale,
+            Point { x: sx / SUBPIXEL_STEPS, y: sy / SUBPIXEL_STEPS })
+        {
+            Some(coverage) => coverage,
+            None           => return GlyphBitmap {
+                left: 0,
+                top: 0,
+                width: 0,
+                coverage: Vec::new(),
+                size: MIN_BITMAP_SIZE,
+                last_used: tick,
+            },
+        };
+
+        let values: Vec<u8> = coverage.values
+            .iter()
+            .map(|&v| (v * 255.0).round() as u8)
+            .collect();
+        GlyphBitmap {
+            left: coverage.left,
+            top: coverage.top,
+            width: coverage.width as usize,
+            size: values.len().max(MIN_BITMAP_SIZE),
+            coverage: values,
+            last_used: tick,
+        }
+    }
+
+    /// Applies the pattern through the given bitmap with its pixel origin at
+    /// the given coordinates.
+    fn blit<C, P, X>(
+        canvas: &mut C,
+        pattern: &P,
+        bitmap: &GlyphBitmap,
+        x: f32,
+        y: f32)
+        where
+            C: Canvas<Pixel=X>,
+            P: Pattern<X>,
+    {
+        if bitmap.width == 0 { return; }
+
+        let rows = bitmap.coverage.chunks(bitmap.width);
+        for (j, row) in rows.enumerate() {
+            for (i, &v) in row.iter().enumerate().filter(|&(_, &v)| v > 0) {
+                pattern.apply(canvas, Point {
+                    x: x + (bitmap.left + i as i32) as f32,
+                    y: y + (bitmap.top + j as i32) as f32,
+                }, v as f32 / 255.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Text decoration metrics.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use brush::Brush;
+use canvas::Canvas;
+use geometry::Point;
+use primitive::line::segment_horizontal;
+use super::FontStyle;
+use super::typeface::Typeface;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// DecorationMetrics
+////////////////////////////////////////////////////////////////////////////////
+/// The position and thickness of a font's underline and strikeout strokes,
+/// in unscaled font units.
+///
+/// Positions give the top of the stroke relative to the baseline, with
+/// positive values lying above it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecorationMetrics {
+    pub underline_position: f32,
+    pub underline_thickness: f32,
+    pub strikeout_position: f32,
+    pub strikeout_thickness: f32,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Decorations
+////////////////////////////////////////////////////////////////////////////////
+/// The scaled placement of each text decoration, given as the offset of the
+/// top of the stroke from the baseline and its thickness, in pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(in primitive::text) struct Decorations {
+    pub(in primitive::text) underline: (f32, f32),
+    pub(in primitive::text) strikethrough: (f32, f32),
+    pub(in primitive::text) overline: (f32, f32),
+}
+
+impl Decorations {
+    /// Returns the `Decorations` for the given font and style, using the
+    /// style's decoration metrics if provided, then the font's own, or scaled
+    /// defaults if neither has any.
+    pub(in primitive::text) fn new(
+        font: &dyn Typeface,
+        font_style: FontStyle)
+        -> Self
+    {
+        let height = font_style.scale.vert;
+        let ascent = font.v_metrics(font_style.scale).ascent;
+
+        let metrics = font_style.decoration_metrics
+            .or_else(|| font.decoration_metrics());
+
+        let (underline, strikethrough) = match metrics {
+            Some(metrics) => {
+                let s = font.scale_for_pixel_height(height);
+                (
+                    (-metrics.underline_position * s,
+                        metrics.underline_thickness * s),
+                    (-metrics.strikeout_position * s,
+                        metrics.strikeout_thickness * s),
+                )
+            },
+            None          => {
+                let thickness = height / 16.0;
+                ((height * 0.08, thickness), (height * -0.3, thickness))
+            },
+        };
+
+        Decorations {
+            underline,
+            strikethrough,
+            // Overlines sit at the top of the line, inside the line height.
+            overline: (-ascent, underline.1),
+        }
+    }
+
+    /// Draws the decorations enabled by the given style between the given
+    /// horizontal positions along the given baseline.
+    pub(in primitive::text) fn draw<C, B, X>(
+        &self,
+        canvas: &mut C,
+        brush: &B,
+        font_style: FontStyle,
+        left: f32,
+        right: f32,
+        baseline: f32)
+        where
+            C: Canvas<Pixel=X>,
+            B: Brush<X>,
+    {
+        let enabled = [
+            (font_style.underline, self.underline),
+            (font_style.strikethrough, self.strikethrough),
+            (font_style.overline, self.overline),
+        ];
+
+        for &(_, (offset, thickness)) in enabled.iter().filter(|e| e.0) {
+            // Draw whole rows of pixels, at least one.
+            let top = (baseline + offset).round();
+            let rows = thickness.round().max(1.0) as u32;
+            for row in 0..rows {
+                let start = Point { x: left, y: top + row as f32 };
+                segment_horizontal(canvas, brush, start, right);
+            }
+        }
+    }
+}
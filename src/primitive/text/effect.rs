@@ -0,0 +1,278 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Text shadow and outline effects.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use brush::Brush;
+use canvas::Canvas;
+use geometry::Point;
+use geometry::Rect;
+use pattern::Pattern;
+use super::PreparedText;
+use super::TextAnchor;
+use super::typeface::Coverage;
+use utilities::clamped;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TextShadow
+////////////////////////////////////////////////////////////////////////////////
+/// A drop shadow drawn beneath text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextShadow<P> {
+    /// The offset of the shadow from the text.
+    pub offset: Point,
+    /// The distance over which the edges of the shadow are blurred.
+    pub blur_radius: f32,
+    /// The pattern used to draw the shadow.
+    pub pattern: P,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TextOutline
+////////////////////////////////////////////////////////////////////////////////
+/// An outline drawn around the glyphs of text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextOutline<P> {
+    /// The width of the outline beyond the edges of the glyphs.
+    pub width: f32,
+    /// The pattern used to draw the outline.
+    pub pattern: P,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TextEffects
+////////////////////////////////////////////////////////////////////////////////
+/// Effects drawn beneath text to keep it readable over busy backgrounds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextEffects<P> {
+    /// The drop shadow of the text, if any. The shadow is cast by the outline
+    /// if there is one.
+    pub shadow: Option<TextShadow<P>>,
+    /// The outline of the text, if any.
+    pub outline: Option<TextOutline<P>>,
+}
+
+impl<P> TextEffects<P> {
+    /// Returns a new `TextEffects` with no effects enabled.
+    #[inline]
+    pub fn new() -> Self {
+        TextEffects { shadow: None, outline: None }
+    }
+
+    /// Returns the `TextEffects` with a drop shadow with the given offset,
+    /// blur radius, and pattern.
+    #[inline]
+    pub fn with_shadow(mut self, offset: Point, blur_radius: f32, pattern: P)
+        -> Self
+    {
+        self.shadow = Some(TextShadow { offset, blur_radius, pattern });
+        self
+    }
+
+    /// Returns the `TextEffects` with an outline of the given width and
+    /// pattern.
+    #[inline]
+    pub fn with_outline(mut self, width: f32, pattern: P) -> Self {
+        self.outline = Some(TextOutline { width, pattern });
+        self
+    }
+}
+
+impl<P> Default for TextEffects<P> {
+    #[inline]
+    fn default() -> Self {
+        TextEffects::new()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// PreparedText effect drawing
+////////////////////////////////////////////////////////////////////////////////
+impl<'f> PreparedText<'f> {
+    /// Draws the text over the given [`TextEffects`].
+    ///
+    /// The shadow is drawn first, then the outline, then the glyphs and their
+    /// decorations. Decorations are drawn without effects.
+    ///
+    /// [`TextEffects`]: struct.TextEffects.html
+    pub fn draw_with_effects<C, P, B, E, X>(
+        &self,
+        canvas: &mut C,
+        pattern: &P,
+        decoration: &B,
+        pt: Point,
+        anchor: TextAnchor,
+        effects: &TextEffects<E>)
+        where
+            C: Canvas<Pixel=X>,
+            P: Pattern<X>,
+            B: Brush<X>,
+            E: Pattern<X>,
+    {
+        let pt = pt - self.anchor_offset(anchor);
+        if let Some(coverage) = self.coverage(pt) {
+            let outline = effects.outline
+                .as_ref()
+                .map(|outline| (outline, dilate(&coverage, outline.width)));
+
+            if let Some(ref shadow) = effects.shadow {
+                let caster = outline.as_ref()
+                    .map(|(_, dilated)| dilated)
+                    .unwrap_or(&coverage);
+                draw_shadow(canvas, shadow, caster);
+            }
+
+            if let Some((outline, dilated)) = outline {
+                draw_coverage(canvas, &outline.pattern, &dilated);
+            }
+        }
+
+        for g in &self.glyphs {
+            g.draw(canvas, pattern, pt);
+        }
+        self.draw_decorations(canvas, decoration, pt);
+    }
+
+    /// Returns the combined coverage of the text's glyphs drawn at the given
+    /// point, or `None` if no pixels are covered.
+    fn coverage(&self, pt: Point) -> Option<Coverage> {
+        let mut pixels = Vec::new();
+        for g in &self.glyphs {
+            g.face.rasterize(g.id, g.scale, pt + g.offset, &mut |pos, v| {
+                pixels.push((pos, v));
+            });
+        }
+        Coverage::from_pixels(pixels)
+    }
+}
+
+
+/// Returns the given coverage expanded outward by the given width.
+fn dilate(coverage: &Coverage, width: f32) -> Coverage {
+    let width = width.max(0.0);
+    let reach = (width + 1.0).ceil() as i32;
+    let mut dilated = Coverage::blank(
+        coverage.left - reach,
+        coverage.top - reach,
+        coverage.width + 2 * reach,
+        coverage.height + 2 * reach);
+
+    for y in dilated.top..(dilated.top + dilated.height) {
+        for x in dilated.left..(dilated.left + dilated.width) {
+            // Take the strongest nearby coverage, fading over the last pixel
+            // of the width.
+            let mut value: f32 = 0.0;
+            for dy in -reach..(reach + 1) {
+                for dx in -reach..(reach + 1) {
+                    let c = coverage.at(x + dx, y + dy);
+                    if c <= 0.0 { continue; }
+                    let distance = ((dx * dx + dy * dy) as f32).sqrt();
+                    let falloff = clamped(width + 1.0 - distance, 0.0, 1.0);
+                    value = value.max(c * falloff);
+                }
+            }
+            if let Some(v) = dilated.at_mut(x, y) { *v = value; }
+        }
+    }
+    dilated
+}
+
+
+/// Returns the given coverage blurred over the given radius.
+fn blur(coverage: &Coverage, radius: f32) -> Coverage {
+    let reach = radius.ceil() as i32;
+    if reach <= 0 { return coverage.clone(); }
+
+    // Use a normalized gaussian kernel spanning two standard deviations.
+    let sigma = radius / 2.0;
+    let mut kernel: Vec<f32> = (-reach..(reach + 1))
+        .map(|i| (-(i * i) as f32 / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let total: f32 = kernel.iter().sum();
+    for k in &mut kernel { *k /= total; }
+
+    let mut horizontal = Coverage::blank(
+        coverage.left - reach,
+        coverage.top,
+        coverage.width + 2 * reach,
+        coverage.height);
+    for y in horizontal.top..(horizontal.top + horizontal.height) {
+        for x in horizontal.left..(horizontal.left + horizontal.width) {
+            let value = kernel.iter()
+                .enumerate()
+                .map(|(i, k)| k * coverage.at(x + i as i32 - reach, y))
+                .sum();
+            if let Some(v) = horizontal.at_mut(x, y) { *v = value; }
+        }
+    }
+
+    let mut blurred = Coverage::blank(
+        horizontal.left,
+        horizontal.top - reach,
+        horizontal.width,
+        horizontal.height + 2 * reach);
+    for y in blurred.top..(blurred.top + blurred.height) {
+        for x in blurred.left..(blurred.left + blurred.width) {
+            let value = kernel.iter()
+                .enumerate()
+                .map(|(i, k)| k * horizontal.at(x, y + i as i32 - reach))
+                .sum();
+            if let Some(v) = blurred.at_mut(x, y) { *v = value; }
+        }
+    }
+    blurred
+}
+
+
+/// Draws the shadow cast by the given coverage.
+fn draw_shadow<C, P, X>(
+    canvas: &mut C,
+    shadow: &TextShadow<P>,
+    caster: &Coverage)
+    where
+        C: Canvas<Pixel=X>,
+        P: Pattern<X>,
+{
+    let blurred = blur(caster, shadow.blur_radius);
+    let offset = shadow.offset;
+
+    // Extend the painted area by a pixel to cover fractional offsets.
+    let rect = Rect {
+        left: (blurred.left as f32 + offset.x).floor(),
+        top: (blurred.top as f32 + offset.y).floor(),
+        right: ((blurred.left + blurred.width) as f32 + offset.x).ceil(),
+        bottom: ((blurred.top + blurred.height) as f32 + offset.y).ceil(),
+    };
+    shadow.pattern.paint(canvas, rect, |pt| blurred.sample(pt - offset));
+}
+
+
+/// Applies the pattern through the given coverage.
+fn draw_coverage<C, P, X>(canvas: &mut C, pattern: &P, coverage: &Coverage)
+    where
+        C: Canvas<Pixel=X>,
+        P: Pattern<X>,
+{
+    for y in coverage.top..(coverage.top + coverage.height) {
+        for x in coverage.left..(coverage.left + coverage.width) {
+            let value = coverage.at(x, y);
+            if value > 0.0 {
+                pattern.apply(canvas, Point {
+                    x: x as f32,
+                    y: y as f32,
+                }, value);
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Font fallback.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use super::typeface::Typeface;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// FontSet
+////////////////////////////////////////////////////////////////////////////////
+/// A primary font followed by a list of fallback fonts.
+///
+/// Each character is drawn with the first font in the set which contains a
+/// glyph for it. Characters which no font contains are drawn with the primary
+/// font's missing glyph. Line metrics and decorations are always taken from
+/// the primary font.
+///
+/// Fonts may be of any [`Typeface`], and different kinds of font may be mixed
+/// within a set.
+///
+/// [`Typeface`]: trait.Typeface.html
+#[derive(Clone)]
+pub struct FontSet<'f> {
+    fonts: Vec<&'f dyn Typeface>,
+}
+
+impl<'f> FontSet<'f> {
+    /// Returns a new `FontSet` with the given primary font and no fallbacks.
+    #[inline]
+    pub fn new<T>(primary: &'f T) -> Self where T: Typeface + 'f {
+        FontSet { fonts: vec![primary] }
+    }
+
+    /// Returns the `FontSet` with the given font appended to its fallbacks.
+    #[inline]
+    pub fn with_fallback<T>(mut self, font: &'f T) -> Self
+        where T: Typeface + 'f
+    {
+        self.push(font);
+        self
+    }
+
+    /// Appends the given font to the fallbacks.
+    #[inline]
+    pub fn push<T>(&mut self, font: &'f T) where T: Typeface + 'f {
+        self.fonts.push(font);
+    }
+
+    /// Returns the primary font.
+    #[inline]
+    pub fn primary(&self) -> &'f dyn Typeface {
+        self.fonts[0]
+    }
+
+    /// Returns the fonts of the set, starting with the primary font.
+    #[inline]
+    pub fn fonts(&self) -> &[&'f dyn Typeface] {
+        &self.fonts[..]
+    }
+
+    /// Returns the index within the set of the font used to draw the given
+    /// character, along with the font.
+    pub fn font_for(&self, character: char) -> (usize, &'f dyn Typeface) {
+        self.fonts.iter()
+            .enumerate()
+            .find(|&(_, font)| font.has_glyph(character))
+            .map(|(i, &font)| (i, font))
+            .unwrap_or((0, self.fonts[0]))
+    }
+}
+
+impl<'f, T> From<&'f T> for FontSet<'f> where T: Typeface + 'f {
+    #[inline]
+    fn from(font: &'f T) -> Self {
+        FontSet::new(font)
+    }
+}
+
+impl<'a, 'f> From<&'a FontSet<'f>> for FontSet<'f> {
+    #[inline]
+    fn from(fonts: &'a FontSet<'f>) -> Self {
+        fonts.clone()
+    }
+}
@@ -0,0 +1,536 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Multi-line text layout.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use super::decoration::Decorations;
+use super::FontSet;
+use super::FontStyle;
+use super::OffsetGlyph;
+use super::OffsetLayoutIter;
+use super::PreparedText;
+use super::typeface::VMetrics;
+use geometry::Point;
+
+// Standard library imports.
+use std::f32;
+use std::ops::Range;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TextAlign
+////////////////////////////////////////////////////////////////////////////////
+/// The horizontal alignment of lines of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextAlign {
+    /// Lines are aligned to the left edge.
+    Left,
+    /// Lines are centered.
+    Center,
+    /// Lines are aligned to the right edge.
+    Right,
+    /// Lines are stretched to both edges by widening the spaces between words.
+    /// The last line of each paragraph is aligned to the left edge.
+    Justify,
+}
+
+impl Default for TextAlign {
+    #[inline]
+    fn default() -> Self {
+        TextAlign::Left
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TextWrap
+////////////////////////////////////////////////////////////////////////////////
+/// The method used to break lines of text which exceed the maximum width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextWrap {
+    /// Lines are only broken at explicit newlines.
+    None,
+    /// Lines are broken after whitespace. Words which are too long to fit on a
+    /// line by themselves are broken between characters.
+    Word,
+    /// Lines are broken between any characters.
+    Character,
+}
+
+impl Default for TextWrap {
+    #[inline]
+    fn default() -> Self {
+        TextWrap::Word
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TextDirection
+////////////////////////////////////////////////////////////////////////////////
+/// The direction in which the characters and lines of text advance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextDirection {
+    /// Characters advance rightward and lines advance downward.
+    LeftToRight,
+    /// Characters advance leftward and lines advance downward. The layout is
+    /// mirrored, so lines aligned left are placed against the right edge, as
+    /// is the last line of each justified paragraph.
+    RightToLeft,
+    /// Characters advance downward in columns, and columns advance leftward.
+    /// Glyphs are drawn upright, each in a cell as tall as the line height of
+    /// its font. Decorations are not drawn.
+    TopToBottom,
+}
+
+impl Default for TextDirection {
+    #[inline]
+    fn default() -> Self {
+        TextDirection::LeftToRight
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TextLayout
+////////////////////////////////////////////////////////////////////////////////
+/// Options controlling the layout of multi-line text.
+///
+/// For vertical text, widths are measured down each column, and alignment is
+/// applied vertically.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextLayout {
+    /// The maximum width of a line, or `None` if lines should only be broken
+    /// at explicit newlines.
+    pub max_width: Option<f32>,
+    /// The method used to break lines which exceed the maximum width.
+    pub wrap: TextWrap,
+    /// The horizontal alignment of the lines. Lines are aligned within the
+    /// maximum width if one is given, or the width of the widest line
+    /// otherwise.
+    pub align: TextAlign,
+    /// A multiplier for the distance between consecutive baselines.
+    pub line_spacing: f32,
+    /// The direction in which characters and lines advance.
+    pub direction: TextDirection,
+}
+
+impl TextLayout {
+    /// Returns a new `TextLayout` which wraps words at the given maximum
+    /// width.
+    #[inline]
+    pub fn new(max_width: f32) -> Self {
+        TextLayout { max_width: Some(max_width), .. Default::default() }
+    }
+}
+
+impl Default for TextLayout {
+    #[inline]
+    fn default() -> Self {
+        TextLayout {
+            max_width: None,
+            wrap: TextWrap::default(),
+            align: TextAlign::default(),
+            line_spacing: 1.0,
+            direction: TextDirection::default(),
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// LineMetrics
+////////////////////////////////////////////////////////////////////////////////
+/// The position and extent of a line of laid out text.
+///
+/// For vertical text, each line is a column and the axes are exchanged: `left`
+/// is the offset of the top of the column from the top of the text, `width` is
+/// the length of the column, and `baseline` is the offset of the column's
+/// center line from the left of the text, with `ascent` and `descent` giving
+/// the distances to its left and right edges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineMetrics {
+    /// The range of glyph indices on the line.
+    pub glyphs: Range<usize>,
+    /// The range of character indices in the source text covered by the line,
+    /// excluding any line breaking newline.
+    pub chars: Range<usize>,
+    /// The offset of the left edge of the line from the left of the text.
+    pub left: f32,
+    /// The offset of the line's baseline from the top of the text.
+    pub baseline: f32,
+    /// The distance from the baseline to the top of the line.
+    pub ascent: f32,
+    /// The distance from the baseline to the bottom of the line. This is
+    /// typically negative.
+    pub descent: f32,
+    /// The advance width of the line, excluding trailing whitespace.
+    pub width: f32,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// prepare_text_layout
+////////////////////////////////////////////////////////////////////////////////
+/// Lays out the given text over multiple lines.
+///
+/// Lines are broken at explicit newlines, and at the maximum width given by the
+/// [`TextLayout`]. Consecutive baselines are separated by the font's line
+/// height and line gap, scaled by the layout's line spacing. The line height is
+/// taken from the tallest font of the [`FontSet`] supplying glyphs to the line,
+/// or from the primary font for empty lines.
+///
+/// [`TextLayout`]: struct.TextLayout.html
+/// [`FontSet`]: struct.FontSet.html
+pub fn prepare_text_layout<'f, F>(
+    fonts: F,
+    font_style: FontStyle,
+    text: &str,
+    layout: TextLayout)
+    -> PreparedText<'f>
+    where F: Into<FontSet<'f>>
+{
+    let fonts = fonts.into();
+    let font = fonts.primary();
+    let v_metrics = font.v_metrics(font_style.scale);
+
+    let mut paragraphs = Vec::new();
+    let mut index_base = 0;
+    for paragraph in text.split('\n') {
+        let char_count = paragraph.chars().count();
+        let paragraph = paragraph.trim_end_matches('\r');
+
+        paragraphs.push(Paragraph {
+            glyphs: OffsetLayoutIter {
+                fonts: &fonts,
+                chars: paragraph.chars().enumerate(),
+                caret: 0.0,
+                font_style,
+                last_glyph: None,
+                index_base,
+            }.collect(),
+            chars: paragraph.chars().collect(),
+            index_base,
+            span: 0,
+        });
+
+        // Skip past the paragraph and its newline.
+        index_base += char_count + 1;
+    }
+
+    let (glyphs, lines) = layout_paragraphs(paragraphs, &[v_metrics], layout);
+
+    PreparedText {
+        glyphs,
+        lines,
+        font_style,
+        v_metrics,
+        decorations: Decorations::new(font, font_style),
+        direction: layout.direction,
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Paragraph
+////////////////////////////////////////////////////////////////////////////////
+/// A paragraph of glyphs laid out on a single line, prior to line breaking.
+pub(in primitive::text) struct Paragraph<'f> {
+    /// The glyphs of the paragraph, offset from the start of the paragraph.
+    pub(in primitive::text) glyphs: Vec<OffsetGlyph<'f>>,
+    /// The character of each glyph.
+    pub(in primitive::text) chars: Vec<char>,
+    /// The index of the paragraph's first character in the source text.
+    pub(in primitive::text) index_base: usize,
+    /// The span in effect at the start of the paragraph, which determines the
+    /// height of the paragraph if it is empty.
+    pub(in primitive::text) span: usize,
+}
+
+
+/// Breaks and aligns the given paragraphs into lines, returning the positioned
+/// glyphs and the metrics of each line. The height of each line is determined
+/// by the tallest typeface supplying its glyphs. The given vertical metrics are
+/// those of each span of the text, and determine the height of empty lines.
+pub(in primitive::text) fn layout_paragraphs<'f>(
+    paragraphs: Vec<Paragraph<'f>>,
+    span_metrics: &[VMetrics],
+    layout: TextLayout)
+    -> (Vec<OffsetGlyph<'f>>, Vec<LineMetrics>)
+{
+    let vertical = layout.direction == TextDirection::TopToBottom;
+    let mut glyphs: Vec<OffsetGlyph> = Vec::new();
+    let mut whitespace: Vec<bool> = Vec::new();
+    let mut lines: Vec<LineMetrics> = Vec::new();
+    // Whether each line ends a paragraph.
+    let mut paragraph_ends: Vec<bool> = Vec::new();
+
+    let mut baseline = 0.0;
+    // The descent and line gap of the previous line.
+    let mut prev_bottom: Option<(f32, f32)> = None;
+    for paragraph in paragraphs {
+        let laid = &paragraph.glyphs;
+        let chars = &paragraph.chars;
+        let index_base = paragraph.index_base;
+
+        // The position and advance of each glyph along the line.
+        let extents: Vec<(f32, f32)> = if vertical {
+            vertical_extents(laid)
+        } else {
+            laid.iter().map(|g| (g.offset.x, g.advance())).collect()
+        };
+
+        let breaks = break_lines(&extents, chars, layout);
+        let break_count = breaks.len();
+        for (n, range) in breaks.into_iter().enumerate() {
+            let start = glyphs.len();
+            let left = extents.get(range.start).map(|e| e.0).unwrap_or(0.0);
+
+            // Measure the line up to the end of its last visible glyph.
+            let width = range.clone()
+                .rev()
+                .find(|&i| !chars[i].is_whitespace())
+                .map(|i| extents[i].0 + extents[i].1 - left)
+                .unwrap_or(0.0);
+
+            // Measure the height of the line from the typefaces of the glyphs
+            // on it. Columns of vertical text are centered on their baselines.
+            let mut metrics = line_metrics(
+                laid[range.clone()].iter().map(|g| g.face.v_metrics(g.scale)),
+                span_metrics[paragraph.span]);
+            if vertical {
+                let half_width = (metrics.ascent - metrics.descent) / 2.0;
+                metrics.ascent = half_width;
+                metrics.descent = -half_width;
+            }
+            baseline += match prev_bottom {
+                Some((descent, line_gap)) => layout.line_spacing
+                    * (metrics.ascent - descent + line_gap),
+                None                      => metrics.ascent,
+            };
+            prev_bottom = Some((metrics.descent, metrics.line_gap));
+
+            for i in range.clone() {
+                let mut glyph = laid[i].clone();
+                glyph.offset.x = extents[i].0 - left;
+                glyph.offset.y = baseline;
+                glyphs.push(glyph);
+                whitespace.push(chars[i].is_whitespace());
+            }
+
+            lines.push(LineMetrics {
+                glyphs: start..glyphs.len(),
+                chars: index_base + range.start .. index_base + range.end,
+                left: 0.0,
+                baseline,
+                ascent: metrics.ascent,
+                descent: metrics.descent,
+                width,
+            });
+            paragraph_ends.push(n + 1 == break_count);
+        }
+    }
+
+    let width = align_width(&lines, layout);
+    align_lines(&mut glyphs, &mut lines, &whitespace, &paragraph_ends, width,
+        layout);
+    match layout.direction {
+        TextDirection::LeftToRight => (),
+        TextDirection::RightToLeft => {
+            mirror_lines(&mut glyphs, &mut lines, width);
+        },
+        TextDirection::TopToBottom => {
+            transpose_lines(&mut glyphs, &mut lines);
+        },
+    }
+    (glyphs, lines)
+}
+
+
+/// Returns the position and advance of each of the given glyphs when stacked
+/// vertically.
+fn vertical_extents(glyphs: &[OffsetGlyph]) -> Vec<(f32, f32)> {
+    let mut caret = 0.0;
+    glyphs.iter()
+        .map(|g| {
+            let start = caret;
+            caret += g.vertical_advance();
+            (start, caret - start)
+        })
+        .collect()
+}
+
+
+/// Returns the combined vertical metrics of the given metrics, or the default
+/// metrics if none are given.
+fn line_metrics<I>(metrics: I, default: VMetrics) -> VMetrics
+    where I: Iterator<Item=VMetrics>
+{
+    metrics.fold(None, |acc: Option<VMetrics>, m| Some(match acc {
+        Some(acc) => VMetrics {
+            ascent: acc.ascent.max(m.ascent),
+            descent: acc.descent.min(m.descent),
+            line_gap: acc.line_gap.max(m.line_gap),
+        },
+        None      => m,
+    })).unwrap_or(default)
+}
+
+
+/// Returns the ranges of glyphs in a laid out paragraph which fall on each
+/// line, given the position and advance of each glyph along the line. At least
+/// one (possibly empty) range is always returned.
+fn break_lines(extents: &[(f32, f32)], chars: &[char], layout: TextLayout)
+    -> Vec<Range<usize>>
+{
+    let count = extents.len();
+    let max_width = match (layout.max_width, layout.wrap) {
+        (Some(max_width), TextWrap::Word)      |
+        (Some(max_width), TextWrap::Character) => max_width,
+        _                                      => f32::INFINITY,
+    };
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    // The index of the first glyph after the most recent whitespace.
+    let mut last_break = None;
+    let mut i = 0;
+    while i < count {
+        let whitespace = chars[i].is_whitespace();
+        let right = extents[i].0 + extents[i].1 - extents[start].0;
+
+        // Whitespace is allowed to hang past the end of a line.
+        if i > start && !whitespace && right > max_width {
+            let brk = match layout.wrap {
+                TextWrap::Word => last_break
+                    .filter(|&b| b > start)
+                    .unwrap_or(i),
+                _              => i,
+            };
+            ranges.push(start..brk);
+            start = brk;
+            last_break = None;
+            // Remeasure the current glyph on the new line.
+            continue;
+        }
+
+        if whitespace { last_break = Some(i + 1); }
+        i += 1;
+    }
+    ranges.push(start..count);
+    ranges
+}
+
+
+/// Returns the width within which the given lines are aligned.
+fn align_width(lines: &[LineMetrics], layout: TextLayout) -> f32 {
+    layout.max_width.unwrap_or_else(|| lines
+        .iter()
+        .map(|line| line.width)
+        .fold(0.0, f32::max))
+}
+
+
+/// Offsets the glyphs on each line according to the layout's alignment within
+/// the given width.
+fn align_lines(
+    glyphs: &mut [OffsetGlyph],
+    lines: &mut [LineMetrics],
+    whitespace: &[bool],
+    paragraph_ends: &[bool],
+    align_width: f32,
+    layout: TextLayout)
+{
+    for (line, &paragraph_end) in lines.iter_mut().zip(paragraph_ends) {
+        let extra = (align_width - line.width).max(0.0);
+        line.left = match layout.align {
+            TextAlign::Left    => 0.0,
+            TextAlign::Center  => extra / 2.0,
+            TextAlign::Right   => extra,
+            TextAlign::Justify => {
+                if !paragraph_end {
+                    justify_line(glyphs, line, whitespace, extra);
+                }
+                0.0
+            },
+        };
+
+        for glyph in &mut glyphs[line.glyphs.clone()] {
+            glyph.offset.x += line.left;
+        }
+    }
+}
+
+
+/// Distributes the given extra width between the words of the given line.
+fn justify_line(
+    glyphs: &mut [OffsetGlyph],
+    line: &mut LineMetrics,
+    whitespace: &[bool],
+    extra: f32)
+{
+    // Only whitespace followed by a visible glyph on the line is widened.
+    let last_visible = match line.glyphs.clone()
+        .rev()
+        .find(|&i| !whitespace[i])
+    {
+        Some(i) => i,
+        None    => return,
+    };
+    let gaps = (line.glyphs.start..last_visible)
+        .filter(|&i| whitespace[i])
+        .count();
+    if gaps == 0 { return; }
+
+    let gap_extra = extra / gaps as f32;
+    let mut shift = 0.0;
+    for i in line.glyphs.clone() {
+        glyphs[i].offset.x += shift;
+        if whitespace[i] && i < last_visible { shift += gap_extra; }
+    }
+    line.width += extra;
+}
+
+
+/// Mirrors the glyphs and lines horizontally within the given width.
+fn mirror_lines(
+    glyphs: &mut [OffsetGlyph],
+    lines: &mut [LineMetrics],
+    width: f32)
+{
+    for glyph in glyphs.iter_mut() {
+        glyph.offset.x = width - glyph.offset.x - glyph.advance();
+    }
+    for line in lines.iter_mut() {
+        line.left = width - line.left - line.width;
+    }
+}
+
+
+/// Converts lines laid out along the horizontal axis into columns of vertical
+/// text, placed from right to left.
+fn transpose_lines(glyphs: &mut [OffsetGlyph], lines: &mut [LineMetrics]) {
+    let width = lines.iter()
+        .map(|line| line.baseline - line.descent)
+        .fold(0.0, f32::max);
+
+    for line in lines.iter_mut() {
+        line.baseline = width - line.baseline;
+        // Center each glyph in the column, with its baseline below the top of
+        // its cell by the ascent of its font.
+        for glyph in &mut glyphs[line.glyphs.clone()] {
+            let ascent = glyph.face.v_metrics(glyph.scale).ascent;
+            glyph.offset = Point {
+                x: line.baseline - glyph.advance() / 2.0,
+                y: glyph.offset.x + ascent,
+            };
+        }
+    }
+}
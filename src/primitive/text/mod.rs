@@ -0,0 +1,765 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Text drawing primitives.
+////////////////////////////////////////////////////////////////////////////////
+
+// Internal modules.
+mod anchor;
+mod bitmap;
+mod cache;
+mod decoration;
+mod effect;
+mod font_set;
+mod layout;
+mod outline;
+mod path;
+mod rich;
+mod subpixel;
+mod truncate;
+mod typeface;
+
+// Local imports.
+use brush::Brush;
+use canvas::Canvas;
+use geometry::Point;
+use geometry::Rect;
+use geometry::Scale;
+use pattern::Pattern;
+use self::decoration::Decorations;
+
+// Exports.
+pub use self::anchor::HorizontalAnchor;
+pub use self::anchor::TextAnchor;
+pub use self::anchor::VerticalAnchor;
+pub use self::bitmap::BitmapFont;
+pub use self::cache::GlyphCache;
+pub use self::decoration::DecorationMetrics;
+pub use self::effect::TextEffects;
+pub use self::effect::TextOutline;
+pub use self::effect::TextShadow;
+pub use self::font_set::FontSet;
+pub use self::layout::LineMetrics;
+pub use self::layout::prepare_text_layout;
+pub use self::layout::TextAlign;
+pub use self::layout::TextDirection;
+pub use self::layout::TextLayout;
+pub use self::layout::TextWrap;
+pub use self::path::TextPath;
+pub use self::rich::prepare_rich_text;
+pub use self::rich::PreparedRichText;
+pub use self::rich::rich_text;
+pub use self::rich::TextSpan;
+pub use self::subpixel::SubpixelOrder;
+pub use self::truncate::prepare_text_fitted;
+pub use self::truncate::TruncateMode;
+pub use self::typeface::Typeface;
+pub use self::typeface::VMetrics;
+
+// External library imports.
+use rusttype;
+
+
+pub type Font<'a> = rusttype::Font<'a>;
+
+////////////////////////////////////////////////////////////////////////////////
+// FontStyle
+////////////////////////////////////////////////////////////////////////////////
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontStyle {
+    scale: Scale,
+    underline: bool,
+    strikethrough: bool,
+    overline: bool,
+    decoration_metrics: Option<DecorationMetrics>,
+}
+
+impl FontStyle {
+    #[inline]
+    pub fn new(
+        scale: Scale,
+        underline: bool)
+        -> Self
+    {
+        FontStyle {
+            scale,
+            underline,
+            strikethrough: false,
+            overline: false,
+            decoration_metrics: None,
+        }
+    }
+
+    /// Returns the `FontStyle` with a strikethrough decoration enabled or
+    /// disabled.
+    #[inline]
+    pub fn with_strikethrough(mut self, strikethrough: bool) -> Self {
+        self.strikethrough = strikethrough;
+        self
+    }
+
+    /// Returns the `FontStyle` with an overline decoration enabled or disabled.
+    #[inline]
+    pub fn with_overline(mut self, overline: bool) -> Self {
+        self.overline = overline;
+        self
+    }
+
+    /// Returns the `FontStyle` with the given [`DecorationMetrics`] used to
+    /// place its decorations instead of the font's own.
+    ///
+    /// [`DecorationMetrics`]: struct.DecorationMetrics.html
+    #[inline]
+    pub fn with_decoration_metrics(mut self, metrics: DecorationMetrics)
+        -> Self
+    {
+        self.decoration_metrics = Some(metrics);
+        self
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TextOptions
+////////////////////////////////////////////////////////////////////////////////
+/// The fonts, pattern, and anchor used to draw text with [`text`] and
+/// [`glyph`].
+///
+/// [`text`]: fn.text.html
+/// [`glyph`]: fn.glyph.html
+#[derive(Clone)]
+pub struct TextOptions<'f, P> {
+    /// The fonts used to draw the text.
+    pub fonts: FontSet<'f>,
+    /// The pattern used to draw the text's glyphs.
+    pub pattern: P,
+    /// The position within the text which is placed at the drawn point.
+    pub anchor: TextAnchor,
+}
+
+impl<'f, P> TextOptions<'f, P> {
+    /// Returns new `TextOptions` drawing with the given fonts and pattern,
+    /// anchored at the top-left of the text.
+    #[inline]
+    pub fn new<F>(fonts: F, pattern: P) -> Self where F: Into<FontSet<'f>> {
+        TextOptions {
+            fonts: fonts.into(),
+            pattern,
+            anchor: TextAnchor::default(),
+        }
+    }
+
+    /// Returns the `TextOptions` with the given [`TextAnchor`].
+    ///
+    /// [`TextAnchor`]: struct.TextAnchor.html
+    #[inline]
+    pub fn with_anchor(mut self, anchor: TextAnchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// glyph
+////////////////////////////////////////////////////////////////////////////////
+#[inline]
+pub fn glyph<'f, C, P, B, X>(
+    canvas: &mut C,
+    options: &TextOptions<'f, P>,
+    decoration: &B,
+    font_style: FontStyle,
+    pt: Point,
+    character: char)
+    where
+        C: Canvas<Pixel=X>,
+        P: Pattern<X>,
+        B: Brush<X>,
+{
+    prepare_glyph(&options.fonts, font_style, character)
+        .draw(canvas, &options.pattern, decoration, pt, options.anchor)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// text
+////////////////////////////////////////////////////////////////////////////////
+#[inline]
+pub fn text<'f, C, P, B, X>(
+    canvas: &mut C,
+    options: &TextOptions<'f, P>,
+    decoration: &B,
+    font_style: FontStyle,
+    pt: Point,
+    text: &str)
+    where
+        C: Canvas<Pixel=X>,
+        P: Pattern<X>,
+        B: Brush<X>,
+{
+    prepare_text(&options.fonts, font_style, text)
+        .draw(canvas, &options.pattern, decoration, pt, options.anchor)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// PreparedText
+////////////////////////////////////////////////////////////////////////////////
+pub struct PreparedText<'f> {
+    glyphs: Vec<OffsetGlyph<'f>>,
+    lines: Vec<LineMetrics>,
+    font_style: FontStyle,
+    v_metrics: VMetrics,
+    decorations: Decorations,
+    direction: TextDirection,
+}
+
+impl<'f> PreparedText<'f> {
+    #[inline]
+    pub fn font_style(&self) -> FontStyle {
+        self.font_style
+    }
+
+    /// Returns the [`TextDirection`] of the text.
+    ///
+    /// [`TextDirection`]: enum.TextDirection.html
+    #[inline]
+    pub fn direction(&self) -> TextDirection {
+        self.direction
+    }
+
+    /// Returns the [`LineMetrics`] for each line of the text.
+    ///
+    /// [`LineMetrics`]: struct.LineMetrics.html
+    #[inline]
+    pub fn lines(&self) -> &[LineMetrics] {
+        &self.lines[..]
+    }
+
+    /// Returns the width of the widest line of the text, or the distance from
+    /// the left of the last column to the right of the first for vertical
+    /// text.
+    #[inline]
+    pub fn width(&self) -> f32 {
+        match self.direction {
+            TextDirection::TopToBottom => self.lines.iter()
+                .map(|line| line.baseline - line.descent)
+                .fold(0.0, f32::max),
+            _                          => self.line_extent(),
+        }
+    }
+
+    /// Returns the distance from the top of the first line of the text to the
+    /// bottom of the last line, or the length of the longest column for
+    /// vertical text.
+    #[inline]
+    pub fn height(&self) -> f32 {
+        match self.direction {
+            TextDirection::TopToBottom => self.line_extent(),
+            _                          => self.lines.last()
+                .map(|line| line.baseline - line.descent)
+                .unwrap_or(self.v_metrics.ascent - self.v_metrics.descent),
+        }
+    }
+
+    /// Returns the greatest distance from the start of the text to the end of
+    /// any of its lines.
+    #[inline]
+    fn line_extent(&self) -> f32 {
+        self.lines.iter()
+            .map(|line| line.left + line.width)
+            .fold(0.0, f32::max)
+    }
+
+    /// Returns the position of the given [`TextAnchor`] relative to the
+    /// top-left of the text. Vertical text has no common baseline, so its
+    /// baseline anchor lies at the top of the text.
+    ///
+    /// [`TextAnchor`]: struct.TextAnchor.html
+    pub fn anchor_offset(&self, anchor: TextAnchor) -> Point {
+        let x = match anchor.horizontal {
+            HorizontalAnchor::Left   => 0.0,
+            HorizontalAnchor::Center => self.width() / 2.0,
+            HorizontalAnchor::Right  => self.width(),
+        };
+        let y = match anchor.vertical {
+            VerticalAnchor::Top      => 0.0,
+            VerticalAnchor::Middle   => self.height() / 2.0,
+            VerticalAnchor::Baseline => match self.direction {
+                TextDirection::TopToBottom => 0.0,
+                _                          => self.lines.first()
+                    .map(|line| line.baseline)
+                    .unwrap_or(self.v_metrics.ascent),
+            },
+            VerticalAnchor::Bottom   => self.height(),
+        };
+        Point { x, y }
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Queries
+    //
+    // All positions are relative to the top-left point the text is drawn at.
+    ////////////////////////////////////////////////////////////////////////////
+
+    /// Returns the number of characters in the source text.
+    #[inline]
+    pub fn char_count(&self) -> usize {
+        self.lines.last().map(|line| line.chars.end).unwrap_or(0)
+    }
+
+    /// Returns the [`Rect`] spanning the advance widths and line heights of
+    /// all lines of the text.
+    ///
+    /// [`Rect`]: ../geometry/struct.Rect.html
+    pub fn advance_rect(&self) -> Rect {
+        let start = self.lines.iter()
+            .map(|line| line.left)
+            .fold(self.line_extent(), f32::min);
+        match self.direction {
+            TextDirection::TopToBottom => Rect {
+                left: 0.0,
+                top: start,
+                right: self.width(),
+                bottom: self.height(),
+            },
+            _                          => Rect {
+                left: start,
+                top: 0.0,
+                right: self.width(),
+                bottom: self.height(),
+            },
+        }
+    }
+
+    /// Returns the smallest [`Rect`] containing all of the inked pixels of the
+    /// text, or `None` if the text has no visible glyphs.
+    ///
+    /// [`Rect`]: ../geometry/struct.Rect.html
+    pub fn ink_rect(&self) -> Option<Rect> {
+        self.glyphs.iter()
+            .filter_map(|g| g.face.bounds(g.id, g.scale).map(|bb| Rect {
+                left: bb.left + g.offset.x,
+                top: bb.top + g.offset.y,
+                right: bb.right + g.offset.x,
+                bottom: bb.bottom + g.offset.y,
+            }))
+            .fold(None, |acc: Option<Rect>, r| Some(match acc {
+                Some(acc) => Rect {
+                    left: acc.left.min(r.left),
+                    top: acc.top.min(r.top),
+                    right: acc.right.max(r.right),
+                    bottom: acc.bottom.max(r.bottom),
+                },
+                None      => r,
+            }))
+    }
+
+    /// Returns the advance [`Rect`] of each glyph, along with the index of the
+    /// glyph's character in the source text. Each `Rect` spans the height of
+    /// the glyph's line, or the width of its column for vertical text.
+    ///
+    /// [`Rect`]: ../geometry/struct.Rect.html
+    pub fn glyph_rects(&self) -> Vec<(usize, Rect)> {
+        self.lines.iter()
+            .flat_map(|line| self.glyphs[line.glyphs.clone()]
+                .iter()
+                .map(move |g| (g.index, self.glyph_rect(line, g))))
+            .collect()
+    }
+
+    /// Returns the advance `Rect` of the given glyph on the given line.
+    fn glyph_rect(&self, line: &LineMetrics, g: &OffsetGlyph) -> Rect {
+        match self.direction {
+            TextDirection::TopToBottom => {
+                let top = g.offset.y - g.face.v_metrics(g.scale).ascent;
+                Rect {
+                    left: line.baseline - line.ascent,
+                    top,
+                    right: line.baseline - line.descent,
+                    bottom: top + g.vertical_advance(),
+                }
+            },
+            _                          => Rect {
+                left: g.offset.x,
+                top: line.baseline - line.ascent,
+                right: g.offset.x + g.advance(),
+                bottom: line.baseline - line.descent,
+            },
+        }
+    }
+
+    /// Returns the positions along the line of the start and end of the given
+    /// glyph's advance, in the direction of the text.
+    fn glyph_span(&self, line: &LineMetrics, g: &OffsetGlyph) -> (f32, f32) {
+        let rect = self.glyph_rect(line, g);
+        match self.direction {
+            TextDirection::LeftToRight => (rect.left, rect.right),
+            TextDirection::RightToLeft => (rect.right, rect.left),
+            TextDirection::TopToBottom => (rect.top, rect.bottom),
+        }
+    }
+
+    /// Returns the endpoints of the caret placed before the character at the
+    /// given index in the source text. An index equal to the character count
+    /// places the caret at the end of the text. The caret spans the height of
+    /// its line, from top to bottom, or the width of its column, from left to
+    /// right, for vertical text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the number of characters in the
+    /// text.
+    pub fn caret(&self, index: usize) -> [Point; 2] {
+        assert!(index <= self.char_count(), "caret index out of bounds");
+
+        // An index at the end of a wrapped line belongs to the start of the
+        // next line.
+        let line = self.lines.iter()
+            .enumerate()
+            .find(|&(n, line)| index < line.chars.end || (
+                index == line.chars.end &&
+                self.lines.get(n + 1).is_none_or(|l| l.chars.start != index)
+            ))
+            .map(|(_, line)| line)
+            .expect("caret line for index in bounds");
+
+        let glyphs = &self.glyphs[line.glyphs.clone()];
+        let along = match glyphs.iter().find(|g| g.index >= index) {
+            Some(g) => self.glyph_span(line, g).0,
+            None    => glyphs.last()
+                .map(|g| self.glyph_span(line, g).1)
+                .unwrap_or(line.left),
+        };
+
+        let start = line.baseline - line.ascent;
+        let end = line.baseline - line.descent;
+        match self.direction {
+            TextDirection::TopToBottom => [
+                Point { x: start, y: along },
+                Point { x: end, y: along },
+            ],
+            _                          => [
+                Point { x: along, y: start },
+                Point { x: along, y: end },
+            ],
+        }
+    }
+
+    /// Returns the index of the character in the source text before which a
+    /// caret should be placed to be nearest to the given [`Point`].
+    ///
+    /// Points above or below the text are treated as lying on the first or
+    /// last line, respectively. For vertical text, points right or left of the
+    /// text are treated as lying on the first or last column.
+    ///
+    /// [`Point`]: ../geometry/struct.Point.html
+    pub fn index_at(&self, pt: Point) -> usize {
+        let vertical = self.direction == TextDirection::TopToBottom;
+        let line = match self.lines.iter()
+            .find(|line| if vertical {
+                pt.x >= line.baseline - line.ascent
+            } else {
+                pt.y < line.baseline - line.descent
+            })
+            .or_else(|| self.lines.last())
+        {
+            Some(line) => line,
+            None       => return 0,
+        };
+
+        // Select the nearest glyph edge.
+        let along = if vertical { pt.y } else { pt.x };
+        self.glyphs[line.glyphs.clone()].iter()
+            .find(|g| {
+                let (start, end) = self.glyph_span(line, g);
+                let center = (start + end) / 2.0;
+                if start <= end { along < center } else { along > center }
+            })
+            .map(|g| g.index)
+            .unwrap_or(line.chars.end)
+    }
+    
+    #[inline]
+    pub fn draw_clone<C, P, B, X>(
+        &self,
+        canvas: &mut C,
+        pattern: &P,
+        decoration: &B,
+        pt: Point,
+        anchor: TextAnchor)
+        where
+            C: Canvas<Pixel=X>,
+            P: Pattern<X>,
+            B: Brush<X>,
+    {
+        self.draw_anchored(canvas, decoration, pt, anchor, |canvas, g, pt| {
+            g.draw(canvas, pattern, pt);
+        });
+    }
+
+    #[inline]
+    pub fn draw<C, P, B, X>(
+        self,
+        canvas: &mut C,
+        pattern: &P,
+        decoration: &B,
+        pt: Point,
+        anchor: TextAnchor)
+        where
+            C: Canvas<Pixel=X>,
+            P: Pattern<X>,
+            B: Brush<X>,
+    {
+        self.draw_clone(canvas, pattern, decoration, pt, anchor);
+    }
+
+    /// Draws the text using the given [`GlyphCache`] to avoid rasterizing
+    /// glyphs which have been drawn before.
+    ///
+    /// [`GlyphCache`]: struct.GlyphCache.html
+    pub fn draw_cached<C, P, B, X>(
+        &self,
+        canvas: &mut C,
+        pattern: &P,
+        decoration: &B,
+        pt: Point,
+        anchor: TextAnchor,
+        cache: &mut GlyphCache<'f>)
+        where
+            C: Canvas<Pixel=X>,
+            P: Pattern<X>,
+            B: Brush<X>,
+    {
+        self.draw_anchored(canvas, decoration, pt, anchor, |canvas, g, pt| {
+            cache.draw_glyph(canvas, pattern, g, pt + g.offset);
+        });
+    }
+
+    /// Draws the text anchored at the given point, calling the given function
+    /// to draw each glyph offset from the top-left of the text.
+    fn draw_anchored<C, B, X, F>(
+        &self,
+        canvas: &mut C,
+        decoration: &B,
+        pt: Point,
+        anchor: TextAnchor,
+        mut draw_glyph: F)
+        where
+            C: Canvas<Pixel=X>,
+            B: Brush<X>,
+            F: FnMut(&mut C, &OffsetGlyph<'f>, Point),
+    {
+        let pt = pt - self.anchor_offset(anchor);
+        for g in &self.glyphs {
+            draw_glyph(canvas, g, pt);
+        }
+        self.draw_decorations(canvas, decoration, pt);
+    }
+
+    /// Draws the enabled decorations of each line of text positioned at the
+    /// given point.
+    ///
+    /// The decorations are placed by the font's decoration metrics, unless the
+    /// [`FontStyle`] overrides them. Fonts without decoration metrics have
+    /// their decorations drawn at default positions scaled by the font size.
+    ///
+    /// [`FontStyle`]: struct.FontStyle.html
+    fn draw_decorations<C, B, X>(&self, canvas: &mut C, brush: &B, pt: Point)
+        where
+            C: Canvas<Pixel=X>,
+            B: Brush<X>,
+    {
+        if self.direction == TextDirection::TopToBottom { return; }
+
+        for line in &self.lines {
+            let left = pt.x + line.left;
+            self.decorations.draw(
+                canvas,
+                brush,
+                self.font_style,
+                left,
+                left + line.width,
+                pt.y + line.baseline);
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// OffsetGlyph
+////////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct OffsetGlyph<'f> {
+    /// The typeface of the glyph.
+    pub(in primitive::text) face: &'f dyn Typeface,
+    /// The id of the glyph within its typeface.
+    pub(in primitive::text) id: u32,
+    /// The scale of the glyph.
+    pub(in primitive::text) scale: Scale,
+    /// The offset of the glyph's origin on the baseline from the top-left of
+    /// the text.
+    pub(in primitive::text) offset: Point,
+    /// The index of the glyph's character in the source text.
+    pub(in primitive::text) index: usize,
+    /// The index of the span containing the glyph, for rich text.
+    pub(in primitive::text) span: usize,
+}
+
+impl<'f> OffsetGlyph<'f> {
+    #[inline]
+    pub fn new(face: &'f dyn Typeface, id: u32, scale: Scale) -> Self {
+        OffsetGlyph {
+            face,
+            id,
+            scale,
+            offset: Point::default(),
+            index: 0,
+            span: 0,
+        }
+    }
+
+    /// Returns the horizontal advance of the glyph.
+    #[inline]
+    pub fn advance(&self) -> f32 {
+        self.face.advance(self.id, self.scale)
+    }
+
+    /// Returns the vertical advance of the glyph in vertical text, which is
+    /// the line height of its font.
+    #[inline]
+    pub(in primitive::text) fn vertical_advance(&self) -> f32 {
+        let v_metrics = self.face.v_metrics(self.scale);
+        v_metrics.ascent - v_metrics.descent
+    }
+
+    /// Draws the glyph offset from the given point.
+    pub fn draw<C, P, X>(&self, canvas: &mut C, pattern: &P, pt: Point)
+        where
+            C: Canvas<Pixel=X>,
+            P: Pattern<X>,
+    {
+        let origin = pt + self.offset;
+        self.face.rasterize(self.id, self.scale, origin, &mut |pos, v| {
+            pattern.apply(canvas, Point {
+                x: pos.x as f32,
+                y: pos.y as f32,
+            }, v);
+        });
+    }
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// prepare_glyph
+////////////////////////////////////////////////////////////////////////////////
+#[inline]
+pub fn prepare_glyph<'f, F>(
+    fonts: F,
+    font_style: FontStyle,
+    character: char)
+    -> PreparedText<'f>
+    where F: Into<FontSet<'f>>
+{
+    let fonts = fonts.into();
+
+    // Layout the glyph geometry using the metrics of the font containing it.
+    let (_, face) = fonts.font_for(character);
+    let v_metrics = face.v_metrics(font_style.scale);
+    let mut glyph = OffsetGlyph::new(
+        face,
+        face.glyph_id(character),
+        font_style.scale);
+    glyph.offset.y = v_metrics.ascent;
+
+    let line = LineMetrics {
+        glyphs: 0..1,
+        chars: 0..1,
+        left: 0.0,
+        baseline: v_metrics.ascent,
+        ascent: v_metrics.ascent,
+        descent: v_metrics.descent,
+        width: glyph.advance(),
+    };
+
+    PreparedText {
+        glyphs: vec![glyph],
+        lines: vec![line],
+        font_style,
+        v_metrics,
+        decorations: Decorations::new(face, font_style),
+        direction: TextDirection::LeftToRight,
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// prepare_text
+////////////////////////////////////////////////////////////////////////////////
+/// Lays out the given text, breaking lines only at explicit newlines.
+///
+/// Text may be laid out with a single [`Font`] or a [`FontSet`]. Each
+/// character is drawn with the first font in the set containing it, and
+/// kerning is applied only between glyphs from the same font.
+///
+/// [`Font`]: type.Font.html
+/// [`FontSet`]: struct.FontSet.html
+#[inline]
+pub fn prepare_text<'f, F>(
+    fonts: F,
+    font_style: FontStyle,
+    text: &str)
+    -> PreparedText<'f>
+    where F: Into<FontSet<'f>>
+{
+    prepare_text_layout(fonts, font_style, text, TextLayout::default())
+}
+
+
+struct OffsetLayoutIter<'a, 'b, 'c> {
+    fonts: &'c FontSet<'a>,
+    chars: ::std::iter::Enumerate<::std::str::Chars<'b>>,
+    caret: f32,
+    font_style: FontStyle,
+    /// The font index and id of the previous glyph.
+    last_glyph: Option<(usize, u32)>,
+    index_base: usize,
+}
+
+impl<'a, 'b, 'c> Iterator for OffsetLayoutIter<'a, 'b, 'c> {
+    type Item = OffsetGlyph<'a>;
+
+    fn next(&mut self) -> Option<OffsetGlyph<'a>> {
+        self.chars.next().map(|(i, c)| {
+            let scale = self.font_style.scale;
+            let (font_index, face) = self.fonts.font_for(c);
+            let id = face.glyph_id(c);
+
+            // Kerning is only applied within runs of the same font.
+            match self.last_glyph {
+                Some((last_font, last)) if last_font == font_index => {
+                    self.caret += face.kerning(scale, last, id);
+                },
+                _ => (),
+            }
+            self.last_glyph = Some((font_index, id));
+            let offset_glyph = OffsetGlyph {
+                face,
+                id,
+                scale,
+                offset: Point { x: self.caret, y: 0.0 },
+                index: self.index_base + i,
+                span: 0,
+            };
+
+            self.caret += offset_glyph.advance();
+            offset_glyph
+        })
+    }
+}
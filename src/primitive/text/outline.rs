@@ -0,0 +1,38 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Text outlines.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use geometry::Path;
+use super::PreparedText;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// PreparedText outlines
+////////////////////////////////////////////////////////////////////////////////
+impl<'f> PreparedText<'f> {
+    /// Returns the combined outlines of the text's glyphs, relative to the
+    /// top-left of the text. Glyphs without outlines are omitted.
+    ///
+    /// The outline may be filled with [`fill_path`] or stroked with
+    /// [`stroke_path`]. Decorations are not included.
+    ///
+    /// [`fill_path`]: fn.fill_path.html
+    /// [`stroke_path`]: fn.stroke_path.html
+    pub fn outline(&self) -> Path {
+        let mut path = Path::new();
+        for g in &self.glyphs {
+            if let Some(outline) = g.face.outline(g.id, g.scale) {
+                path.extend(&outline.translated(g.offset));
+            }
+        }
+        path
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Text along a path.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use canvas::Canvas;
+use geometry::Path;
+use geometry::Point;
+use geometry::Rect;
+use pattern::Pattern;
+use super::OffsetGlyph;
+use super::PreparedText;
+use super::TextAnchor;
+use super::typeface::Coverage;
+
+// Standard library imports.
+use std::f32;
+
+
+/// The maximum distance between a curve and the polyline approximating it.
+const FLATTEN_TOLERANCE: f32 = 0.1;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TextPath
+////////////////////////////////////////////////////////////////////////////////
+/// A polyline parametrized by distance, along which text can be drawn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextPath {
+    points: Vec<Point>,
+    /// The distance along the path of each point.
+    distances: Vec<f32>,
+}
+
+impl TextPath {
+    /// Returns a new `TextPath` passing through the given points.
+    pub fn new<I>(points: I) -> Self where I: IntoIterator<Item=Point> {
+        let mut path = TextPath { points: Vec::new(), distances: Vec::new() };
+        for pt in points {
+            path.push(pt);
+        }
+        path
+    }
+
+    /// Returns a new `TextPath` following the contours of the given [`Path`]
+    /// in order. Consecutive contours are joined by a straight line.
+    ///
+    /// [`Path`]: ../geometry/struct.Path.html
+    pub fn from_path(path: &Path) -> Self {
+        TextPath::new(path.flattened(FLATTEN_TOLERANCE)
+            .into_iter()
+            .flat_map(|contour| contour.into_iter()))
+    }
+
+    /// Returns a new `TextPath` following the quadratic Bézier curve with the
+    /// given endpoints and control point.
+    pub fn quadratic(start: Point, control: Point, end: Point) -> Self {
+        let mut path = Path::new();
+        path.move_to(start);
+        path.quad_to(control, end);
+        TextPath::from_path(&path)
+    }
+
+    /// Returns a new `TextPath` following the cubic Bézier curve with the
+    /// given endpoints and control points.
+    pub fn cubic(start: Point, c1: Point, c2: Point, end: Point) -> Self {
+        let mut path = Path::new();
+        path.move_to(start);
+        path.cubic_to(c1, c2, end);
+        TextPath::from_path(&path)
+    }
+
+    /// Appends a point to the end of the path. Points coinciding with the end
+    /// of the path are ignored.
+    pub fn push(&mut self, pt: Point) {
+        let distance = match self.points.last() {
+            Some(&last) if last == pt => return,
+            Some(&last)               => self.length() + last.distance(pt),
+            None                      => 0.0,
+        };
+        self.points.push(pt);
+        self.distances.push(distance);
+    }
+
+    /// Returns the points of the path.
+    #[inline]
+    pub fn points(&self) -> &[Point] {
+        &self.points[..]
+    }
+
+    /// Returns the length of the path.
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.distances.last().cloned().unwrap_or(0.0)
+    }
+
+    /// Returns the point at the given distance along the path, along with the
+    /// angle of the path's direction there in radians. Returns `None` if the
+    /// distance lies outside of the path.
+    pub fn at(&self, distance: f32) -> Option<(Point, f32)> {
+        if self.points.len() < 2 || distance < 0.0 || distance > self.length() {
+            return None;
+        }
+
+        // Find the segment containing the distance.
+        let end = match self.distances.iter().position(|&d| d >= distance) {
+            Some(0) => 1,
+            Some(i) => i,
+            None    => self.points.len() - 1,
+        };
+        let (a, b) = (self.points[end - 1], self.points[end]);
+        let (da, db) = (self.distances[end - 1], self.distances[end]);
+
+        let pt = a.lerp(b, (distance - da) / (db - da));
+        let angle = (b.y - a.y).atan2(b.x - a.x);
+        Some((pt, angle))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// PreparedText
+////////////////////////////////////////////////////////////////////////////////
+impl<'f> PreparedText<'f> {
+    /// Draws the text along the given [`TextPath`], placing the text's anchor
+    /// at the given distance along the path.
+    ///
+    /// Each glyph is rotated to the direction of the path at the center of its
+    /// advance, and offset from the path by its distance below the anchor.
+    /// Glyphs whose centers lie beyond either end of the path are not drawn,
+    /// nor are decorations.
+    ///
+    /// [`TextPath`]: struct.TextPath.html
+    pub fn draw_along_path<C, P, X>(
+        &self,
+        canvas: &mut C,
+        pattern: &P,
+        path: &TextPath,
+        distance: f32,
+        anchor: TextAnchor)
+        where
+            C: Canvas<Pixel=X>,
+            P: Pattern<X>,
+    {
+        let anchor = self.anchor_offset(anchor);
+        for g in &self.glyphs {
+            let half_advance = g.advance() / 2.0;
+            let along = distance + g.offset.x - anchor.x + half_advance;
+            let (center, angle) = match path.at(along) {
+                Some(placement) => placement,
+                None            => continue,
+            };
+
+            // Move from the center of the advance back to the glyph origin,
+            // then across the path to the glyph's baseline.
+            let (sin, cos) = angle.sin_cos();
+            let across = g.offset.y - anchor.y;
+            let origin = Point {
+                x: center.x - cos * half_advance - sin * across,
+                y: center.y - sin * half_advance + cos * across,
+            };
+            draw_rotated_glyph(canvas, pattern, g, origin, angle);
+        }
+    }
+}
+
+
+/// Draws the given glyph rotated by the given angle about its origin, placing
+/// the origin at the given point.
+fn draw_rotated_glyph<'f, C, P, X>(
+    canvas: &mut C,
+    pattern: &P,
+    glyph: &OffsetGlyph<'f>,
+    origin: Point,
+    angle: f32)
+    where
+        C: Canvas<Pixel=X>,
+        P: Pattern<X>,
+{
+    // Rasterize the upright glyph at the subpixel offset of the origin, so that
+    // unrotated glyphs are sampled exactly.
+    let shift = Point {
+        x: origin.x - origin.x.floor(),
+        y: origin.y - origin.y.floor(),
+    };
+    let coverage = match Coverage::rasterize(
+        glyph.face,
+        glyph.id,
+        glyph.scale,
+        shift)
+    {
+        Some(coverage) => coverage,
+        None           => return,
+    };
+
+    // Find the bounds of the rotated bitmap.
+    let (sin, cos) = angle.sin_cos();
+    let min = Point {
+        x: coverage.left as f32 - shift.x,
+        y: coverage.top as f32 - shift.y,
+    };
+    let max = Point {
+        x: (coverage.left + coverage.width) as f32 - shift.x,
+        y: (coverage.top + coverage.height) as f32 - shift.y,
+    };
+    let corners = [
+            (min.x, min.y),
+            (max.x, min.y),
+            (min.x, max.y),
+            (max.x, max.y),
+        ]
+        .iter()
+        .map(|&(x, y)| Point {
+            x: origin.x + x * cos - y * sin,
+            y: origin.y + x * sin + y * cos,
+        })
+        .collect::<Vec<_>>();
+    let rect = Rect {
+        left: corners.iter().map(|p| p.x).fold(f32::INFINITY, f32::min),
+        top: corners.iter().map(|p| p.y).fold(f32::INFINITY, f32::min),
+        right: corners.iter().map(|p| p.x).fold(f32::NEG_INFINITY, f32::max),
+        bottom: corners.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max),
+    };
+
+    pattern.paint(canvas, rect, |pt| {
+        // Rotate the sample point back into the upright bitmap, then sample it
+        // bilinearly relative to the bitmap's pixel centers.
+        let (dx, dy) = (pt.x - origin.x, pt.y - origin.y);
+        coverage.sample(Point {
+            x: dx * cos + dy * sin + shift.x,
+            y: -dx * sin + dy * cos + shift.y,
+        })
+    });
+}
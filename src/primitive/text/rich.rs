@@ -0,0 +1,307 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Rich text drawing primitives.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use brush::Brush;
+use canvas::Canvas;
+use geometry::Point;
+use geometry::Scale;
+use pattern::Pattern;
+use super::decoration::Decorations;
+use super::layout::layout_paragraphs;
+use super::layout::Paragraph;
+use super::FontSet;
+use super::FontStyle;
+use super::OffsetGlyph;
+use super::PreparedText;
+use super::TextAnchor;
+use super::TextDirection;
+use super::TextLayout;
+use super::typeface::face_id;
+use super::typeface::Typeface;
+
+// Standard library imports.
+use std::mem;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TextSpan
+////////////////////////////////////////////////////////////////////////////////
+/// A run of text drawn with its own fonts, style, pattern, and decoration
+/// brush.
+pub struct TextSpan<'t, 'f, P, B> {
+    /// The text of the span.
+    pub text: &'t str,
+    /// The fonts used to draw the span.
+    pub fonts: FontSet<'f>,
+    /// The style of the span.
+    pub font_style: FontStyle,
+    /// The pattern used to draw the span's glyphs.
+    pub pattern: P,
+    /// The brush used to draw the span's decorations.
+    pub decoration: B,
+}
+
+impl<'t, 'f, P, B> TextSpan<'t, 'f, P, B> {
+    /// Returns a new `TextSpan`.
+    #[inline]
+    pub fn new<F>(
+        text: &'t str,
+        fonts: F,
+        font_style: FontStyle,
+        pattern: P,
+        decoration: B)
+        -> Self
+        where F: Into<FontSet<'f>>
+    {
+        TextSpan {
+            text,
+            fonts: fonts.into(),
+            font_style,
+            pattern,
+            decoration,
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// rich_text
+////////////////////////////////////////////////////////////////////////////////
+/// Draws the given spans of text, breaking lines only at explicit newlines.
+///
+/// # Panics
+///
+/// Panics if no spans are given.
+#[inline]
+pub fn rich_text<'t, 'f, C, P, B, X, I>(
+    canvas: &mut C,
+    spans: I,
+    pt: Point,
+    anchor: TextAnchor)
+    where
+        C: Canvas<Pixel=X>,
+        P: Pattern<X>,
+        B: Brush<X>,
+        I: IntoIterator<Item=TextSpan<'t, 'f, P, B>>,
+{
+    prepare_rich_text(spans, TextLayout::default())
+        .draw(canvas, pt, anchor)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// PreparedRichText
+////////////////////////////////////////////////////////////////////////////////
+/// Rich text which has been laid out for drawing.
+pub struct PreparedRichText<'f, P, B> {
+    text: PreparedText<'f>,
+    spans: Vec<SpanStyle<P, B>>,
+}
+
+/// The drawing style of a laid out span.
+struct SpanStyle<P, B> {
+    pattern: P,
+    decoration: B,
+    font_style: FontStyle,
+    decorations: Decorations,
+}
+
+impl<'f, P, B> PreparedRichText<'f, P, B> {
+    /// Returns the laid out text, which provides the measurement and
+    /// hit-testing queries of the combined spans.
+    ///
+    /// The queries use the style of the first span wherever a single style is
+    /// needed.
+    #[inline]
+    pub fn text(&self) -> &PreparedText<'f> {
+        &self.text
+    }
+
+    /// Returns the number of spans in the text.
+    #[inline]
+    pub fn span_count(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Draws the text with each span's pattern and decoration brush.
+    pub fn draw<C, X>(&self, canvas: &mut C, pt: Point, anchor: TextAnchor)
+        where
+            C: Canvas<Pixel=X>,
+            P: Pattern<X>,
+            B: Brush<X>,
+    {
+        let pt = pt - self.text.anchor_offset(anchor);
+        for g in &self.text.glyphs {
+            g.draw(canvas, &self.spans[g.span].pattern, pt);
+        }
+
+        // Decorations are only drawn for horizontal text.
+        if self.text.direction == TextDirection::TopToBottom { return; }
+
+        for line in &self.text.lines {
+            let line_start = pt.x + line.left;
+            let end = line_start + line.width;
+            let glyphs = &self.text.glyphs[line.glyphs.clone()];
+
+            // Decorate each run of glyphs from the same span.
+            let mut start = 0;
+            while start < glyphs.len() {
+                let span = glyphs[start].span;
+                let count = glyphs[start..]
+                    .iter()
+                    .take_while(|g| g.span == span)
+                    .count();
+                let run = &glyphs[start..start + count];
+
+                // Right-to-left runs are mirrored, so find their extent from
+                // all of their glyphs.
+                let left = run.iter()
+                    .map(|g| pt.x + g.offset.x)
+                    .fold(end, f32::min)
+                    .max(line_start);
+                let right = run.iter()
+                    .map(|g| pt.x + g.offset.x + g.advance())
+                    .fold(line_start, f32::max)
+                    .min(end);
+                if right > left {
+                    let style = &self.spans[span];
+                    style.decorations.draw(
+                        canvas,
+                        &style.decoration,
+                        style.font_style,
+                        left,
+                        right,
+                        pt.y + line.baseline);
+                }
+                start += count;
+            }
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// prepare_rich_text
+////////////////////////////////////////////////////////////////////////////////
+/// Lays out the given spans of text together over multiple lines.
+///
+/// The spans share baselines, and the height of each line is determined by the
+/// tallest span on it. Kerning is applied across span boundaries between
+/// glyphs of the same font and scale.
+///
+/// # Panics
+///
+/// Panics if no spans are given.
+pub fn prepare_rich_text<'t, 'f, P, B, I>(spans: I, layout: TextLayout)
+    -> PreparedRichText<'f, P, B>
+    where I: IntoIterator<Item=TextSpan<'t, 'f, P, B>>
+{
+    let spans: Vec<_> = spans.into_iter().collect();
+    assert!(!spans.is_empty(), "rich text requires at least one span");
+
+    let mut paragraphs = Vec::new();
+    let mut current = Paragraph {
+        glyphs: Vec::new(),
+        chars: Vec::new(),
+        index_base: 0,
+        span: 0,
+    };
+    let mut caret = 0.0;
+    let mut last_glyph: Option<(&dyn Typeface, Scale, u32)> = None;
+    let mut index = 0;
+
+    for (span_index, span) in spans.iter().enumerate() {
+        let scale = span.font_style.scale;
+        for c in span.text.chars() {
+            if c == '\n' {
+                if current.chars.last() == Some(&'\r') {
+                    current.chars.pop();
+                    current.glyphs.pop();
+                }
+                let next = Paragraph {
+                    glyphs: Vec::new(),
+                    chars: Vec::new(),
+                    index_base: index + 1,
+                    span: span_index,
+                };
+                paragraphs.push(mem::replace(&mut current, next));
+                caret = 0.0;
+                last_glyph = None;
+                index += 1;
+                continue;
+            }
+
+            let (_, face) = span.fonts.font_for(c);
+            let id = face.glyph_id(c);
+
+            // Kern across span boundaries only if the font and scale match.
+            match last_glyph {
+                Some((last_face, last_scale, last))
+                    if same_face(last_face, face) && last_scale == scale =>
+                {
+                    caret += face.kerning(scale, last, id);
+                },
+                _ => (),
+            }
+            last_glyph = Some((face, scale, id));
+
+            let offset_glyph = OffsetGlyph {
+                face,
+                id,
+                scale,
+                offset: Point { x: caret, y: 0.0 },
+                index,
+                span: span_index,
+            };
+            caret += offset_glyph.advance();
+            current.glyphs.push(offset_glyph);
+            current.chars.push(c);
+            index += 1;
+        }
+    }
+    paragraphs.push(current);
+
+    let span_metrics: Vec<_> = spans.iter()
+        .map(|span| span.fonts.primary().v_metrics(span.font_style.scale))
+        .collect();
+    let (glyphs, lines) = layout_paragraphs(paragraphs, &span_metrics, layout);
+
+    let first = &spans[0];
+    let text = PreparedText {
+        glyphs,
+        lines,
+        font_style: first.font_style,
+        v_metrics: span_metrics[0],
+        decorations: Decorations::new(first.fonts.primary(), first.font_style),
+        direction: layout.direction,
+    };
+
+    let spans = spans.into_iter()
+        .map(|span| SpanStyle {
+            decorations: Decorations::new(
+                span.fonts.primary(),
+                span.font_style),
+            pattern: span.pattern,
+            decoration: span.decoration,
+            font_style: span.font_style,
+        })
+        .collect();
+
+    PreparedRichText { text, spans }
+}
+
+
+/// Returns `true` if the given typefaces are the same object.
+#[inline]
+fn same_face(a: &dyn Typeface, b: &dyn Typeface) -> bool {
+    face_id(a) == face_id(b)
+}
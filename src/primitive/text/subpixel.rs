@@ -0,0 +1,146 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Subpixel (LCD) text rendering.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use brush::Brush;
+use canvas::Canvas;
+use geometry::Point;
+use geometry::Scale;
+use pattern::Pattern;
+use super::OffsetGlyph;
+use super::PreparedText;
+use super::TextAnchor;
+use super::typeface::Coverage;
+
+
+/// The number of subpixels in each pixel.
+const SUBPIXELS: i32 = 3;
+
+/// The weights with which each subpixel's coverage is spread over itself and
+/// its neighbors, to reduce color fringing.
+const FILTER: [f32; 5] = [
+    1.0 / 9.0,
+    2.0 / 9.0,
+    3.0 / 9.0,
+    2.0 / 9.0,
+    1.0 / 9.0,
+];
+
+
+////////////////////////////////////////////////////////////////////////////////
+// SubpixelOrder
+////////////////////////////////////////////////////////////////////////////////
+/// The left-to-right order of the color subpixels within each pixel of a
+/// display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SubpixelOrder {
+    /// Red, green, then blue.
+    Rgb,
+    /// Blue, green, then red.
+    Bgr,
+}
+
+impl Default for SubpixelOrder {
+    #[inline]
+    fn default() -> Self {
+        SubpixelOrder::Rgb
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// PreparedText subpixel drawing
+////////////////////////////////////////////////////////////////////////////////
+impl<'f> PreparedText<'f> {
+    /// Draws the text with subpixel antialiasing for a display whose pixels
+    /// are divided horizontally into color subpixels in the given order.
+    ///
+    /// Glyphs are rasterized at three times the horizontal resolution and the
+    /// coverage of each subpixel is applied to the corresponding color channel
+    /// using [`Pattern::apply_subpixel`]. Decorations are drawn normally.
+    ///
+    /// [`Pattern::apply_subpixel`]: trait.Pattern.html#method.apply_subpixel
+    pub fn draw_subpixel<C, P, B, X>(
+        &self,
+        canvas: &mut C,
+        pattern: &P,
+        decoration: &B,
+        pt: Point,
+        anchor: TextAnchor,
+        order: SubpixelOrder)
+        where
+            C: Canvas<Pixel=X>,
+            P: Pattern<X>,
+            B: Brush<X>,
+    {
+        let pt = pt - self.anchor_offset(anchor);
+        for g in &self.glyphs {
+            draw_subpixel_glyph(canvas, pattern, g, pt + g.offset, order);
+        }
+        self.draw_decorations(canvas, decoration, pt);
+    }
+}
+
+
+/// Draws the given glyph with its origin at the given point using subpixel
+/// antialiasing.
+fn draw_subpixel_glyph<'f, C, P, X>(
+    canvas: &mut C,
+    pattern: &P,
+    glyph: &OffsetGlyph<'f>,
+    origin: Point,
+    order: SubpixelOrder)
+    where
+        C: Canvas<Pixel=X>,
+        P: Pattern<X>,
+{
+    let subpixels = SUBPIXELS as f32;
+    let coverage = match Coverage::rasterize(
+        glyph.face,
+        glyph.id,
+        Scale::new(glyph.scale.horz * subpixels, glyph.scale.vert),
+        Point { x: origin.x * subpixels, y: origin.y })
+    {
+        Some(coverage) => coverage,
+        None           => return,
+    };
+
+    // Filtering spreads the coverage two subpixels beyond either side of the
+    // rasterized glyph.
+    let spread = FILTER.len() as i32 / 2;
+    let left = ((coverage.left - spread) as f32 / subpixels).floor() as i32;
+    let right = ((coverage.left + coverage.width + spread) as f32 / subpixels)
+        .ceil() as i32;
+
+    for y in coverage.top..(coverage.top + coverage.height) {
+        for x in left..right {
+            let mut channels = [0.0; 3];
+            for (i, channel) in channels.iter_mut().enumerate() {
+                let subpixel = x * SUBPIXELS + i as i32;
+                let filtered: f32 = FILTER.iter()
+                    .enumerate()
+                    .map(|(k, w)| {
+                        w * coverage.at(subpixel + k as i32 - spread, y)
+                    })
+                    .sum();
+                *channel = filtered.min(1.0);
+            }
+            if order == SubpixelOrder::Bgr { channels.reverse(); }
+
+            if channels.iter().any(|&c| c > 0.0) {
+                pattern.apply_subpixel(canvas, Point {
+                    x: x as f32,
+                    y: y as f32,
+                }, channels);
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Text truncation.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use super::FontSet;
+use super::FontStyle;
+use super::prepare_text;
+use super::PreparedText;
+
+
+/// The string inserted in place of truncated text.
+const ELLIPSIS: &str = "…";
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TruncateMode
+////////////////////////////////////////////////////////////////////////////////
+/// The part of a text which is removed when truncating it to fit a width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TruncateMode {
+    /// Characters are removed from the end of the text.
+    End,
+    /// Characters are removed from the start of the text.
+    Start,
+    /// Characters are removed from the middle of the text.
+    Middle,
+}
+
+impl Default for TruncateMode {
+    #[inline]
+    fn default() -> Self {
+        TruncateMode::End
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// prepare_text_fitted
+////////////////////////////////////////////////////////////////////////////////
+/// Lays out the given text, truncating it with an ellipsis if its width would
+/// exceed the given maximum width.
+///
+/// As many characters as possible are kept, and whitespace next to the
+/// ellipsis is removed. If not even the ellipsis fits, the returned text is
+/// empty. Character indices in the returned text refer to the truncated text.
+pub fn prepare_text_fitted<'f, F>(
+    fonts: F,
+    font_style: FontStyle,
+    text: &str,
+    max_width: f32,
+    mode: TruncateMode)
+    -> PreparedText<'f>
+    where F: Into<FontSet<'f>>
+{
+    let fonts = fonts.into();
+    let full = prepare_text(&fonts, font_style, text);
+    if full.width() <= max_width { return full; }
+
+    let chars: Vec<char> = text.chars().collect();
+    let prepare_kept = |kept| prepare_text(
+        &fonts,
+        font_style,
+        &truncated(&chars, kept, mode));
+
+    // Find the most characters which can be kept while fitting.
+    let (mut low, mut high) = (0, chars.len());
+    while low < high {
+        let mid = high - (high - low) / 2;
+        if prepare_kept(mid).width() <= max_width {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    let fitted = prepare_kept(low);
+    if fitted.width() <= max_width {
+        fitted
+    } else {
+        prepare_text(&fonts, font_style, "")
+    }
+}
+
+
+/// Returns the given characters with all but the given number of them replaced
+/// by an ellipsis.
+fn truncated(chars: &[char], kept: usize, mode: TruncateMode) -> String {
+    let (head, tail) = match mode {
+        TruncateMode::End    => (kept, 0),
+        TruncateMode::Start  => (0, kept),
+        TruncateMode::Middle => (kept - kept / 2, kept / 2),
+    };
+
+    let head: String = chars[..head].iter().collect();
+    let tail: String = chars[chars.len() - tail..].iter().collect();
+    format!("{}{}{}", head.trim_end(), ELLIPSIS, tail.trim_start())
+}
@@ -0,0 +1,371 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Font backend abstraction.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use geometry::Path;
+use geometry::Point;
+use geometry::Position;
+use geometry::Rect;
+use geometry::Scale;
+use super::decoration::DecorationMetrics;
+use utilities::lerp;
+
+// External library imports.
+use owned_ttf_parser::AsFaceRef;
+use rusttype::GlyphId;
+use rusttype;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// VMetrics
+////////////////////////////////////////////////////////////////////////////////
+/// The vertical metrics of a typeface at a particular scale.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct VMetrics {
+    /// The distance from the baseline to the top of the line.
+    pub ascent: f32,
+    /// The distance from the baseline to the bottom of the line. This is
+    /// typically negative.
+    pub descent: f32,
+    /// The recommended gap between the bottom of one line and the top of the
+    /// next.
+    pub line_gap: f32,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Typeface
+////////////////////////////////////////////////////////////////////////////////
+/// A font backend providing glyph metrics and coverage for laying out and
+/// drawing text.
+///
+/// Glyphs are identified by typeface-specific ids. All positions are in
+/// pixels, with the glyph origin lying on the baseline and `y` increasing
+/// downward.
+pub trait Typeface {
+    /// Returns the id of the glyph for the given character. If the typeface
+    /// has no glyph for the character, the id of its missing glyph is
+    /// returned.
+    fn glyph_id(&self, character: char) -> u32;
+
+    /// Returns `true` if the typeface has a glyph for the given character.
+    fn has_glyph(&self, character: char) -> bool;
+
+    /// Returns the vertical metrics of the typeface at the given scale.
+    fn v_metrics(&self, scale: Scale) -> VMetrics;
+
+    /// Returns the horizontal advance of the given glyph at the given scale.
+    fn advance(&self, glyph: u32, scale: Scale) -> f32;
+
+    /// Returns the kerning adjustment between the given pair of glyphs at the
+    /// given scale.
+    #[inline]
+    fn kerning(&self, _scale: Scale, _first: u32, _second: u32) -> f32 {
+        0.0
+    }
+
+    /// Returns the bounds of the inked area of the given glyph at the given
+    /// scale, relative to the glyph origin, or `None` if the glyph is empty.
+    fn bounds(&self, glyph: u32, scale: Scale) -> Option<Rect>;
+
+    /// Returns the factor converting the typeface's design units to pixels at
+    /// the given pixel height.
+    fn scale_for_pixel_height(&self, height: f32) -> f32;
+
+    /// Returns the position and thickness of the typeface's underline and
+    /// strikeout strokes, or `None` if the typeface does not provide them.
+    #[inline]
+    fn decoration_metrics(&self) -> Option<DecorationMetrics> {
+        None
+    }
+
+    /// Rasterizes the given glyph at the given scale with its origin at the
+    /// given point, calling the `coverage` function with the position and
+    /// coverage of each covered pixel.
+    fn rasterize(
+        &self,
+        glyph: u32,
+        scale: Scale,
+        origin: Point,
+        coverage: &mut dyn FnMut(Position, f32));
+
+    /// Returns the outline of the given glyph at the given scale, relative to
+    /// the glyph origin, or `None` if the glyph has no outline. Typefaces
+    /// without scalable outlines always return `None`.
+    #[inline]
+    fn outline(&self, _glyph: u32, _scale: Scale) -> Option<Path> {
+        None
+    }
+}
+
+/// Returns an id identifying the given typeface object by its address.
+#[inline]
+pub(in primitive::text) fn face_id(face: &dyn Typeface) -> usize {
+    // Use only the data pointer, as vtables may be duplicated.
+    face as *const dyn Typeface as *const u8 as usize
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// rusttype::Font
+////////////////////////////////////////////////////////////////////////////////
+/// Converts a talc `Scale` into a rusttype `Scale`.
+#[inline]
+fn rusttype_scale(scale: Scale) -> rusttype::Scale {
+    rusttype::Scale { x: scale.horz, y: scale.vert }
+}
+
+impl<'a> Typeface for rusttype::Font<'a> {
+    #[inline]
+    fn glyph_id(&self, character: char) -> u32 {
+        self.glyph(character).id().0 as u32
+    }
+
+    #[inline]
+    fn has_glyph(&self, character: char) -> bool {
+        // Glyph 0 is the font's `.notdef` glyph.
+        self.glyph(character).id() != GlyphId(0)
+    }
+
+    #[inline]
+    fn v_metrics(&self, scale: Scale) -> VMetrics {
+        let v_metrics = rusttype::Font::v_metrics(self, rusttype_scale(scale));
+        VMetrics {
+            ascent: v_metrics.ascent,
+            descent: v_metrics.descent,
+            line_gap: v_metrics.line_gap,
+        }
+    }
+
+    #[inline]
+    fn advance(&self, glyph: u32, scale: Scale) -> f32 {
+        self.glyph(GlyphId(glyph as _))
+            .scaled(rusttype_scale(scale))
+            .h_metrics()
+            .advance_width
+    }
+
+    #[inline]
+    fn kerning(&self, scale: Scale, first: u32, second: u32) -> f32 {
+        self.pair_kerning(
+            rusttype_scale(scale),
+            GlyphId(first as _),
+            GlyphId(second as _))
+    }
+
+    fn bounds(&self, glyph: u32, scale: Scale) -> Option<Rect> {
+        self.glyph(GlyphId(glyph as _))
+            .scaled(rusttype_scale(scale))
+            .exact_bounding_box()
+            .map(|bb| Rect {
+                left: bb.min.x,
+                top: bb.min.y,
+                right: bb.max.x,
+                bottom: bb.max.y,
+            })
+    }
+
+    #[inline]
+    fn scale_for_pixel_height(&self, height: f32) -> f32 {
+        rusttype::Font::scale_for_pixel_height(self, height)
+    }
+
+    fn decoration_metrics(&self) -> Option<DecorationMetrics> {
+        let face = match *self {
+            rusttype::Font::Ref(ref face)   => &**face,
+            rusttype::Font::Owned(ref face) => face.as_face_ref(),
+        };
+        let underline = face.underline_metrics()?;
+        let strikeout = face.strikeout_metrics()?;
+
+        Some(DecorationMetrics {
+            underline_position: underline.position as f32,
+            underline_thickness: underline.thickness as f32,
+            strikeout_position: strikeout.position as f32,
+            strikeout_thickness: strikeout.thickness as f32,
+        })
+    }
+
+    fn rasterize(
+        &self,
+        glyph: u32,
+        scale: Scale,
+        origin: Point,
+        coverage: &mut dyn FnMut(Position, f32))
+    {
+        let positioned = self.glyph(GlyphId(glyph as _))
+            .scaled(rusttype_scale(scale))
+            .positioned(rusttype::point(origin.x, origin.y));
+
+        if let Some(bounding_box) = positioned.pixel_bounding_box() {
+            positioned.draw(|x, y, v| coverage(Position {
+                x: x as i32 + bounding_box.min.x,
+                y: y as i32 + bounding_box.min.y,
+            }, v));
+        }
+    }
+
+    fn outline(&self, glyph: u32, scale: Scale) -> Option<Path> {
+        let mut builder = OutlineBuilder { path: Path::new() };
+        let built = self.glyph(GlyphId(glyph as _))
+            .scaled(rusttype_scale(scale))
+            .build_outline(&mut builder);
+
+        if built && !builder.path.is_empty() {
+            Some(builder.path)
+        } else {
+            None
+        }
+    }
+}
+
+
+/// Collects a rusttype glyph outline into a `Path`.
+struct OutlineBuilder {
+    path: Path,
+}
+
+impl rusttype::OutlineBuilder for OutlineBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.path.move_to(Point { x, y });
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.path.line_to(Point { x, y });
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.path.quad_to(Point { x: x1, y: y1 }, Point { x, y });
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.path.cubic_to(
+            Point { x: x1, y: y1 },
+            Point { x: x2, y: y2 },
+            Point { x, y });
+    }
+
+    fn close(&mut self) {
+        self.path.close();
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Coverage
+////////////////////////////////////////////////////////////////////////////////
+/// A rasterized glyph's coverage.
+#[derive(Debug, Clone)]
+pub(in primitive::text) struct Coverage {
+    /// The position of the top-left pixel of the coverage.
+    pub(in primitive::text) left: i32,
+    pub(in primitive::text) top: i32,
+    pub(in primitive::text) width: i32,
+    pub(in primitive::text) height: i32,
+    /// The row-major coverage values.
+    pub(in primitive::text) values: Vec<f32>,
+}
+
+impl Coverage {
+    /// Returns a new `Coverage` of the given size with no coverage.
+    pub(in primitive::text) fn blank(
+        left: i32,
+        top: i32,
+        width: i32,
+        height: i32)
+        -> Self
+    {
+        Coverage {
+            left,
+            top,
+            width,
+            height,
+            values: vec![0.0; (width * height) as usize],
+        }
+    }
+
+    /// Rasterizes the given glyph into a `Coverage`, returning `None` if the
+    /// glyph covers no pixels.
+    pub(in primitive::text) fn rasterize(
+        face: &dyn Typeface,
+        glyph: u32,
+        scale: Scale,
+        origin: Point)
+        -> Option<Self>
+    {
+        let mut pixels = Vec::new();
+        face.rasterize(glyph, scale, origin, &mut |pos, v| {
+            pixels.push((pos, v));
+        });
+        Coverage::from_pixels(pixels)
+    }
+
+    /// Returns the `Coverage` of the given pixels, returning `None` if there
+    /// are no pixels. The coverage of repeated pixels is summed.
+    pub(in primitive::text) fn from_pixels(pixels: Vec<(Position, f32)>)
+        -> Option<Self>
+    {
+        if pixels.is_empty() { return None; }
+
+        let (mut left, mut top) = (i32::MAX, i32::MAX);
+        let (mut right, mut bottom) = (i32::MIN, i32::MIN);
+        for &(pos, _) in &pixels {
+            left = left.min(pos.x);
+            top = top.min(pos.y);
+            right = right.max(pos.x + 1);
+            bottom = bottom.max(pos.y + 1);
+        }
+
+        let (width, height) = (right - left, bottom - top);
+        let mut coverage = Coverage::blank(left, top, width, height);
+        for (pos, v) in pixels {
+            let value = coverage.at_mut(pos.x, pos.y)
+                .expect("pixel within coverage bounds");
+            *value = (*value + v).min(1.0);
+        }
+        Some(coverage)
+    }
+
+    /// Returns the coverage of the given pixel, or zero if it lies outside of
+    /// the coverage.
+    #[inline]
+    pub(in primitive::text) fn at(&self, x: i32, y: i32) -> f32 {
+        let (x, y) = (x - self.left, y - self.top);
+        if x < 0 || y < 0 || x >= self.width || y >= self.height { return 0.0; }
+        self.values[(y * self.width + x) as usize]
+    }
+
+    /// Returns a mutable reference to the coverage of the given pixel, or
+    /// `None` if it lies outside of the coverage.
+    #[inline]
+    pub(in primitive::text) fn at_mut(&mut self, x: i32, y: i32)
+        -> Option<&mut f32>
+    {
+        let (x, y) = (x - self.left, y - self.top);
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        self.values.get_mut((y * self.width + x) as usize)
+    }
+
+    /// Returns the bilinearly interpolated coverage at the given point,
+    /// relative to the pixel centers.
+    pub(in primitive::text) fn sample(&self, pt: Point) -> f32 {
+        let (u, v) = (pt.x - 0.5, pt.y - 0.5);
+        let (x, y) = (u.floor(), v.floor());
+        let (fx, fy) = (u - x, v - y);
+        let (x, y) = (x as i32, y as i32);
+
+        let top = lerp(self.at(x, y), self.at(x + 1, y), fx);
+        let bottom = lerp(self.at(x, y + 1), self.at(x + 1, y + 1), fx);
+        lerp(top, bottom, fy)
+    }
+}
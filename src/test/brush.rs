@@ -0,0 +1,270 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Tests for drawing brushes.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use brush::Brush;
+use brush::DarkenBrush;
+use brush::EraserBrush;
+use brush::LightenBrush;
+use brush::RoundBrush;
+use brush::SoftBrush;
+use brush::SquareBrush;
+use brush::StampBrush;
+use brush::XorBrush;
+use geometry::Point;
+use primitive::point;
+use primitive::segment;
+use test::TestCanvas;
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// `SquareBrush` tests
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+pub fn square_brush_interior() {
+    let mut c = TestCanvas::square(5);
+
+    point(&mut c, &SquareBrush::new(0xFF, 3), Point { x: 2.0, y: 2.0 });
+
+    assert_eq!(c.buffer, [
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xFF, 0xFF, 0xFF, 0x00,
+        0x00, 0xFF, 0xFF, 0xFF, 0x00,
+        0x00, 0xFF, 0xFF, 0xFF, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
+
+#[test]
+pub fn square_brush_edge() {
+    let mut c = TestCanvas::square(5);
+
+    point(&mut c, &SquareBrush::new(0xFF, 2), Point { x: 0.0, y: 0.0 });
+
+    assert_eq!(c.buffer, [
+        0xFF, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
+
+#[test]
+pub fn square_brush_size() {
+    assert_eq!(Brush::<u32>::size(&SquareBrush::new(0xFF, 4)), (4, 4));
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// `RoundBrush` tests
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+pub fn round_brush_interior() {
+    let mut c = TestCanvas::square(5);
+
+    point(&mut c, &RoundBrush::new(0xFF, 2.0), Point { x: 2.0, y: 2.0 });
+
+    assert_eq!(c.buffer, [
+        0x00, 0x00, 0xFF, 0x00, 0x00,
+        0x00, 0xFF, 0xFF, 0xFF, 0x00,
+        0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+        0x00, 0xFF, 0xFF, 0xFF, 0x00,
+        0x00, 0x00, 0xFF, 0x00, 0x00,
+    ])
+}
+
+#[test]
+pub fn round_brush_zero_radius() {
+    let mut c = TestCanvas::square(5);
+
+    point(&mut c, &RoundBrush::new(0xFF, 0.0), Point { x: 1.0, y: 3.0 });
+
+    assert_eq!(c.buffer, [
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xFF, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
+
+#[test]
+pub fn round_brush_size() {
+    assert_eq!(Brush::<u32>::size(&RoundBrush::new(0xFF, 2.5)), (5, 5));
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// `StampBrush` tests
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+pub fn stamp_brush_interior() {
+    let mut c = TestCanvas::square(5);
+
+    let stamp = StampBrush::new(0xFF, 3, vec![
+        true,  false, true,
+        false, true,  false,
+    ]);
+    point(&mut c, &stamp, Point { x: 2.0, y: 2.0 });
+
+    assert_eq!(c.buffer, [
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xFF, 0x00, 0xFF, 0x00,
+        0x00, 0x00, 0xFF, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
+
+#[test]
+#[should_panic]
+pub fn stamp_brush_ragged_mask() {
+    let _ = StampBrush::new(0xFF, 2, vec![true, false, true]);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// `SoftBrush` tests
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+pub fn soft_brush_hard_edge() {
+    let mut c = TestCanvas::square(5);
+
+    point(&mut c, &SoftBrush::new(0xFF, 2.0, 1.0), Point { x: 2.0, y: 2.0 });
+
+    // Center is fully covered, the edge half covered, and corners uncovered.
+    assert_eq!(c.buffer[12], 0xFF);
+    assert_eq!(c.buffer[10], 0x7F);
+    assert_eq!(c.buffer[0], 0x00);
+}
+
+#[test]
+pub fn soft_brush_feathered() {
+    let mut c = TestCanvas::square(5);
+
+    point(&mut c, &SoftBrush::new(0xFF, 2.0, 0.0), Point { x: 2.0, y: 2.0 });
+
+    // Coverage falls off from the center.
+    assert!(c.buffer[12] < 0xFF);
+    assert!(c.buffer[11] < c.buffer[12]);
+    assert!(c.buffer[10] < c.buffer[11]);
+    assert!(c.buffer[10] > 0x00);
+}
+
+#[test]
+pub fn soft_brush_size() {
+    assert_eq!(Brush::<u32>::size(&SoftBrush::new(0xFF, 2.0, 0.5)), (5, 5));
+}
+
+#[test]
+pub fn soft_brush_size_matches_footprint() {
+    for &radius in &[0.0, 1.0, 2.0, 2.7, 4.0] {
+        let mut c = TestCanvas::square(15);
+        let brush = SoftBrush::new(0xFF, radius, 1.0);
+
+        point(&mut c, &brush, Point { x: 7.0, y: 7.0 });
+
+        // Measure the painted span along the center row and column.
+        let row = c.buffer[7 * 15..8 * 15].iter()
+            .filter(|&&p| p != 0)
+            .count() as u32;
+        let column = (0..15)
+            .filter(|&y| c.buffer[y * 15 + 7] != 0)
+            .count() as u32;
+        assert_eq!(Brush::<u32>::size(&brush), (row, column));
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Read-modify-write brush tests
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+pub fn eraser_brush_reduces_alpha() {
+    let mut c = TestCanvas::square(5);
+    c.buffer[6] = u32::from_bytes([0x10, 0x20, 0x30, 0xFF]);
+
+    point(&mut c, &EraserBrush::new(0.5), Point { x: 1.0, y: 1.0 });
+
+    assert_eq!(c.buffer[6], u32::from_bytes([0x10, 0x20, 0x30, 0x7F]));
+}
+
+#[test]
+pub fn eraser_brush_default_erases() {
+    let mut c = TestCanvas::square(5);
+    c.buffer[6] = u32::from_bytes([0x10, 0x20, 0x30, 0xFF]);
+
+    point(&mut c, &EraserBrush::default(), Point { x: 1.0, y: 1.0 });
+
+    assert_eq!(c.buffer[6], u32::from_bytes([0x10, 0x20, 0x30, 0x00]));
+}
+
+#[test]
+pub fn xor_brush_redraw_restores() {
+    let mut c = TestCanvas::square(5);
+    c.buffer[6] = 0x12;
+    let before = c.buffer.clone();
+
+    let endpoints = [Point { x: 0.0, y: 1.0 }, Point { x: 3.0, y: 1.0 }];
+    segment(&mut c, &XorBrush::invert(), endpoints);
+    assert_eq!(c.buffer[6], 0x12 ^ u32::from_bytes([0xFF, 0xFF, 0xFF, 0x00]));
+
+    segment(&mut c, &XorBrush::invert(), endpoints);
+    assert_eq!(c.buffer, before);
+}
+
+#[test]
+pub fn xor_brush_stroke_inverts_once() {
+    let mut c = TestCanvas::square(5);
+    let vertices = [
+        Point { x: 0.5, y: 1.5 },
+        Point { x: 3.5, y: 1.5 },
+        Point { x: 3.5, y: 3.5 },
+    ];
+
+    XorBrush::new(0xFF).stroke(&mut c, &vertices);
+
+    assert_eq!(c.buffer, [
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0xFF, 0xFF, 0xFF, 0xFF, 0x00,
+        0x00, 0x00, 0x00, 0xFF, 0x00,
+        0x00, 0x00, 0x00, 0xFF, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ]);
+
+    XorBrush::new(0xFF).stroke(&mut c, &vertices);
+    assert!(c.buffer.iter().all(|&p| p == 0));
+}
+
+#[test]
+pub fn lighten_brush() {
+    let mut c = TestCanvas::square(5);
+    c.buffer[6] = u32::from_bytes([0x10, 0x80, 0x30, 0x40]);
+
+    let brush = LightenBrush::new(u32::from_bytes([0x20, 0x20, 0x20, 0xFF]));
+    point(&mut c, &brush, Point { x: 1.0, y: 1.0 });
+
+    assert_eq!(c.buffer[6], u32::from_bytes([0x20, 0x80, 0x30, 0x40]));
+}
+
+#[test]
+pub fn darken_brush() {
+    let mut c = TestCanvas::square(5);
+    c.buffer[6] = u32::from_bytes([0x10, 0x80, 0x30, 0x40]);
+
+    let brush = DarkenBrush::new(u32::from_bytes([0x20, 0x20, 0x20, 0xFF]));
+    point(&mut c, &brush, Point { x: 1.0, y: 1.0 });
+
+    assert_eq!(c.buffer[6], u32::from_bytes([0x10, 0x20, 0x20, 0x40]));
+}
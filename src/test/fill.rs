@@ -0,0 +1,143 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Tests for fill drawing primitives.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use geometry::Path;
+use geometry::Point;
+use geometry::Rect;
+use primitive::fill_masked;
+use primitive::fill_path;
+use test::TestCanvas;
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// `fill_masked` tests
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+pub fn fill_masked_interior() {
+    let mut c = TestCanvas::square(5);
+
+    fill_masked(&mut c, &0xFF,
+        Rect { left: 1.0, top: 1.0, right: 3.0, bottom: 3.0 },
+        |_| 1.0);
+
+    assert_eq!(c.buffer, [
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xFF, 0xFF, 0x00, 0x00,
+        0x00, 0xFF, 0xFF, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
+
+#[test]
+pub fn fill_masked_exterior() {
+    let mut c = TestCanvas::square(5);
+
+    fill_masked(&mut c, &0xFF,
+        Rect { left: -4.0, top: -4.0, right: -1.0, bottom: -1.0 },
+        |_| 1.0);
+
+    assert_eq!(c.buffer, [0x00; 25].to_vec())
+}
+
+#[test]
+pub fn fill_masked_coverage() {
+    let mut c = TestCanvas::square(5);
+
+    // Only the pixels whose centers lie left of x = 2 are covered.
+    fill_masked(&mut c, &0xFF,
+        Rect { left: 0.0, top: 0.0, right: 4.0, bottom: 2.0 },
+        |pt| if pt.x < 2.0 { 1.0 } else { 0.0 });
+
+    assert_eq!(c.buffer, [
+        0xFF, 0xFF, 0x00, 0x00, 0x00,
+        0xFF, 0xFF, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// `fill_path` tests
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+pub fn fill_path_interior() {
+    let mut c = TestCanvas::square(5);
+    let mut path = Path::new();
+    path.move_to(Point { x: 1.0, y: 1.0 });
+    path.line_to(Point { x: 3.0, y: 1.0 });
+    path.line_to(Point { x: 3.0, y: 3.0 });
+    path.line_to(Point { x: 1.0, y: 3.0 });
+
+    fill_path(&mut c, &0xFF, &path);
+
+    assert_eq!(c.buffer, [
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xFF, 0xFF, 0x00, 0x00,
+        0x00, 0xFF, 0xFF, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
+
+#[test]
+pub fn fill_path_nonzero_winding() {
+    let mut c = TestCanvas::square(5);
+    let square = |path: &mut Path, left: f32, right: f32| {
+        path.move_to(Point { x: left, y: left });
+        path.line_to(Point { x: right, y: left });
+        path.line_to(Point { x: right, y: right });
+        path.line_to(Point { x: left, y: right });
+        path.close();
+    };
+    // Overlapping contours with the same winding fill their union.
+    let mut path = Path::new();
+    square(&mut path, 0.0, 3.0);
+    square(&mut path, 2.0, 4.0);
+
+    fill_path(&mut c, &0xFF, &path);
+
+    assert_eq!(c.buffer, [
+        0xFF, 0xFF, 0xFF, 0x00, 0x00,
+        0xFF, 0xFF, 0xFF, 0x00, 0x00,
+        0xFF, 0xFF, 0xFF, 0xFF, 0x00,
+        0x00, 0x00, 0xFF, 0xFF, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
+
+#[test]
+pub fn fill_path_far_off_canvas() {
+    let mut c = TestCanvas::square(5);
+    // Only the rows of the path within the canvas are sampled.
+    let mut path = Path::new();
+    path.move_to(Point { x: 1.0, y: -1.0e9 });
+    path.line_to(Point { x: 3.0, y: -1.0e9 });
+    path.line_to(Point { x: 3.0, y: 3.0 });
+    path.line_to(Point { x: 1.0, y: 3.0 });
+
+    fill_path(&mut c, &0xFF, &path);
+
+    assert_eq!(c.buffer, [
+        0x00, 0xFF, 0xFF, 0x00, 0x00,
+        0x00, 0xFF, 0xFF, 0x00, 0x00,
+        0x00, 0xFF, 0xFF, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
@@ -10,20 +10,478 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 // Local imports.
+use canvas::Canvas;
 use geometry::extend_segment_to_rect;
+use geometry::IRect;
+use geometry::Path;
+use geometry::PathCommand;
 use geometry::Point;
+use geometry::Position;
 use geometry::Rect;
+use geometry::Segment;
 use geometry::intersect_segment_with_segment;
 use geometry::intersect_line_with_segment;
 use geometry::clip_line_to_rect;
 use geometry::clip_segment_to_rect;
 use geometry::Intersection;
+use geometry::BooleanOp;
+use geometry::FillRule;
+use geometry::Orientation;
+use geometry::polygon;
+use test::TestCanvas;
+use utilities::lerp;
 
 // Standard library imports.
 use std::f64::consts::PI;
 
 
 
+////////////////////////////////////////////////////////////////////////////////
+// Segment
+////////////////////////////////////////////////////////////////////////////////
+#[test]
+fn segment_measurements() {
+    let segment = Segment::new(Point::new(1.0, 2.0), Point::new(4.0, 6.0));
+
+    assert_eq!(segment.length(), 5.0);
+    assert_eq!(segment.midpoint(), Point::new(2.5, 4.0));
+    assert_eq!(segment.direction(), Point::new(0.6, 0.8));
+    assert_eq!(segment.normal(), Point::new(-0.8, 0.6));
+    assert_eq!(segment.point_at(0.0), segment.start);
+    assert_eq!(segment.point_at(1.0), segment.end);
+    assert_eq!(segment.point_at(2.0), Point::new(7.0, 10.0));
+}
+
+#[test]
+fn segment_closest_point() {
+    let segment = Segment::new(Point::new(0.0, 0.0), Point::new(10.0, 0.0));
+
+    assert_eq!(segment.closest_point(Point::new(4.0, 3.0)),
+        Point::new(4.0, 0.0));
+    assert_eq!(segment.distance_to(Point::new(4.0, 3.0)), 3.0);
+
+    // Points beyond the ends are nearest to the endpoints.
+    assert_eq!(segment.closest_point(Point::new(-3.0, 4.0)),
+        Point::new(0.0, 0.0));
+    assert_eq!(segment.distance_to(Point::new(-3.0, 4.0)), 5.0);
+    assert_eq!(segment.closest_point(Point::new(12.0, -1.0)),
+        Point::new(10.0, 0.0));
+}
+
+#[test]
+fn segment_degenerate() {
+    let segment = Segment::new(Point::new(2.0, 3.0), Point::new(2.0, 3.0));
+
+    assert!(segment.is_degenerate());
+    assert_eq!(segment.length(), 0.0);
+    assert_eq!(segment.direction(), Point::new(0.0, 0.0));
+    assert_eq!(segment.closest_point(Point::new(5.0, 7.0)),
+        Point::new(2.0, 3.0));
+    assert_eq!(segment.distance_to(Point::new(5.0, 7.0)), 5.0);
+}
+
+#[test]
+fn segment_reversed_and_conversions() {
+    let a = Point::new(1.0, 2.0);
+    let b = Point::new(3.0, 4.0);
+    let segment = Segment::from([a, b]);
+
+    assert_eq!(segment, Segment::new(a, b));
+    assert_eq!(segment.reversed(), Segment::new(b, a));
+    assert_eq!(segment.reversed().reversed(), segment);
+    assert_eq!(<[Point; 2]>::from(segment), [a, b]);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Rect
+////////////////////////////////////////////////////////////////////////////////
+#[test]
+fn rect_constructors() {
+    let rect = Rect { left: 10.0, top: 20.0, right: 50.0, bottom: 40.0 };
+
+    assert_eq!(Rect::from_points(Point::new(50.0, 20.0),
+        Point::new(10.0, 40.0)), rect);
+    assert_eq!(Rect::from_center_size(Point::new(30.0, 30.0), (40.0, 20.0)),
+        rect);
+    assert_eq!(rect.center(), Point::new(30.0, 30.0));
+    assert_eq!(rect.width(), 40.0);
+    assert_eq!(rect.height(), 20.0);
+    assert_eq!(rect.area(), 800.0);
+}
+
+#[test]
+fn rect_intersection_and_union() {
+    let a = Rect { left: 0.0, top: 0.0, right: 10.0, bottom: 10.0 };
+    let b = Rect { left: 5.0, top: -5.0, right: 15.0, bottom: 5.0 };
+
+    assert!(a.intersects(&b));
+    assert_eq!(a.intersection(&b),
+        Some(Rect { left: 5.0, top: 0.0, right: 10.0, bottom: 5.0 }));
+    assert_eq!(a.union(&b),
+        Rect { left: 0.0, top: -5.0, right: 15.0, bottom: 10.0 });
+
+    // Rects sharing only an edge have no points in common.
+    let c = Rect { left: 10.0, top: 0.0, right: 20.0, bottom: 10.0 };
+    assert!(!a.intersects(&c));
+    assert_eq!(a.intersection(&c), None);
+}
+
+#[test]
+fn rect_empty() {
+    let rect = Rect { left: 10.0, top: 0.0, right: 10.0, bottom: 5.0 };
+
+    assert!(rect.is_empty());
+    assert_eq!(rect.area(), 0.0);
+    assert!(!rect.intersects(&rect));
+
+    let inverted = Rect { left: 10.0, top: 0.0, right: 5.0, bottom: 5.0 };
+    assert!(inverted.is_empty());
+    assert_eq!(inverted.area(), 0.0);
+}
+
+#[test]
+fn rect_inflate_deflate_translate() {
+    let rect = Rect { left: 0.0, top: 0.0, right: 10.0, bottom: 10.0 };
+
+    assert_eq!(rect.inflated(2.0, 1.0),
+        Rect { left: -2.0, top: -1.0, right: 12.0, bottom: 11.0 });
+    assert_eq!(rect.deflated(2.0, 1.0),
+        Rect { left: 2.0, top: 1.0, right: 8.0, bottom: 9.0 });
+    assert!(rect.deflated(5.0, 0.0).is_empty());
+    assert_eq!(rect.translated(Point::new(3.0, -4.0)),
+        Rect { left: 3.0, top: -4.0, right: 13.0, bottom: 6.0 });
+}
+
+#[test]
+fn rect_round() {
+    let rect = Rect { left: 0.4, top: -1.6, right: 10.5, bottom: 3.2 };
+
+    assert_eq!(rect.round(), IRect { left: 0, top: -2, right: 11, bottom: 4 });
+
+    let whole = Rect { left: 1.0, top: 2.0, right: 3.0, bottom: 4.0 };
+    assert_eq!(whole.round(), IRect { left: 1, top: 2, right: 3, bottom: 4 });
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// IRect
+////////////////////////////////////////////////////////////////////////////////
+#[test]
+fn irect_from_rect() {
+    let rect = Rect { left: -1.5, top: 0.0, right: 2.25, bottom: 3.0 };
+
+    assert_eq!(IRect::from(rect),
+        IRect { left: -2, top: 0, right: 3, bottom: 3 });
+    assert_eq!(Rect::from(IRect::from(rect)),
+        Rect { left: -2.0, top: 0.0, right: 3.0, bottom: 3.0 });
+
+    // Empty rects cover no pixels.
+    let empty = Rect { left: 2.5, top: 1.0, right: 2.5, bottom: 4.0 };
+    assert!(IRect::from(empty).is_empty());
+    assert_eq!(IRect::from(empty).positions().count(), 0);
+}
+
+#[test]
+fn irect_matches_aligned_pixels() {
+    let rect = Rect { left: 0.75, top: 1.5, right: 3.25, bottom: 2.0 };
+    let irect = IRect::from(rect);
+    let mut c = TestCanvas::square(5);
+
+    // Mark the pixel aligned to a grid of points within the rect.
+    for j in 0..16 {
+        for i in 0..16 {
+            let pt = Point {
+                x: lerp(rect.left, rect.right, i as f32 / 16.0),
+                y: lerp(rect.top, rect.bottom, j as f32 / 16.0),
+            };
+            if let Some(p) = c.aligned_pixel_mut(pt) { *p = 0xFF; }
+        }
+    }
+
+    let canvas_pixels = IRect { left: 0, top: 0, right: 5, bottom: 5 };
+    for pos in canvas_pixels {
+        let marked = c.buffer[pos.y as usize * c.stride + pos.x as usize];
+        assert_eq!(marked == 0xFF, irect.contains(pos), "{:?}", pos);
+    }
+}
+
+#[test]
+fn irect_positions_row_major() {
+    let rect = IRect { left: 1, top: -1, right: 4, bottom: 1 };
+    let positions = rect.positions();
+
+    assert_eq!(positions.len(), 6);
+    assert_eq!(positions.collect::<Vec<_>>(), vec![
+        Position::new(1, -1), Position::new(2, -1), Position::new(3, -1),
+        Position::new(1, 0), Position::new(2, 0), Position::new(3, 0),
+    ]);
+    assert_eq!(rect.area(), 6);
+    assert_eq!(IRect::from_positions(Position::new(3, 0), Position::new(1, -1)),
+        rect);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// polygon
+////////////////////////////////////////////////////////////////////////////////
+#[test]
+fn polygon_area_and_orientation() {
+    let square = [
+        Point::new(0.0, 0.0),
+        Point::new(4.0, 0.0),
+        Point::new(4.0, 4.0),
+        Point::new(0.0, 4.0),
+    ];
+    let mut reversed = square;
+    reversed.reverse();
+
+    assert_eq!(polygon::signed_area(&square), 16.0);
+    assert_eq!(polygon::signed_area(&reversed), -16.0);
+    assert_eq!(polygon::orientation(&square), Orientation::Clockwise);
+    assert_eq!(polygon::orientation(&reversed),
+        Orientation::Counterclockwise);
+    assert_eq!(polygon::orientation(&square[..2]), Orientation::Degenerate);
+    assert_eq!(polygon::signed_area(&[]), 0.0);
+}
+
+#[test]
+fn polygon_centroid_and_bounds() {
+    let triangle = [
+        Point::new(0.0, 0.0),
+        Point::new(6.0, 0.0),
+        Point::new(0.0, 3.0),
+    ];
+
+    assert_eq!(polygon::centroid(&triangle), Some(Point::new(2.0, 1.0)));
+    assert_eq!(polygon::centroid(&triangle[..2]), None);
+    assert_eq!(polygon::bounds(&triangle),
+        Some(Rect { left: 0.0, top: 0.0, right: 6.0, bottom: 3.0 }));
+    assert_eq!(polygon::bounds(&[]), None);
+}
+
+#[test]
+fn polygon_convexity() {
+    let square = [
+        Point::new(0.0, 0.0),
+        Point::new(2.0, 0.0),
+        Point::new(4.0, 0.0),
+        Point::new(4.0, 4.0),
+        Point::new(0.0, 4.0),
+    ];
+    let arrow = [
+        Point::new(0.0, 0.0),
+        Point::new(4.0, 2.0),
+        Point::new(0.0, 4.0),
+        Point::new(1.0, 2.0),
+    ];
+    let star = [
+        Point::new(2.0, 0.0),
+        Point::new(3.2, 4.0),
+        Point::new(0.0, 1.5),
+        Point::new(4.0, 1.5),
+        Point::new(0.8, 4.0),
+    ];
+
+    assert!(polygon::is_convex(&square));
+    assert!(!polygon::is_convex(&arrow));
+    assert!(!polygon::is_convex(&star));
+    assert!(!polygon::is_convex(&square[..2]));
+}
+
+#[test]
+fn polygon_contains_fill_rules() {
+    let star = [
+        Point::new(2.0, 0.0),
+        Point::new(3.2, 4.0),
+        Point::new(0.0, 1.5),
+        Point::new(4.0, 1.5),
+        Point::new(0.8, 4.0),
+    ];
+    let center = Point::new(2.0, 2.0);
+    let point = Point::new(2.0, 0.5);
+    let outside = Point::new(0.2, 3.5);
+
+    // The center of a star is wound twice.
+    assert_eq!(polygon::winding_number(&star, center).abs(), 2);
+    assert!(polygon::contains(&star, center, FillRule::NonZero));
+    assert!(!polygon::contains(&star, center, FillRule::EvenOdd));
+
+    assert!(polygon::contains(&star, point, FillRule::NonZero));
+    assert!(polygon::contains(&star, point, FillRule::EvenOdd));
+
+    assert!(!polygon::contains(&star, outside, FillRule::NonZero));
+    assert!(!polygon::contains(&star, outside, FillRule::EvenOdd));
+}
+
+#[test]
+fn polygon_clip_to_rect() {
+    let rect = Rect { left: 0.0, top: 0.0, right: 4.0, bottom: 4.0 };
+    let diamond = [
+        Point::new(2.0, -2.0),
+        Point::new(6.0, 2.0),
+        Point::new(2.0, 6.0),
+        Point::new(-2.0, 2.0),
+    ];
+
+    let clipped = polygon::clip_to_rect(&diamond, rect);
+    assert_eq!(polygon::signed_area(&clipped), 16.0);
+    assert_eq!(polygon::bounds(&clipped), Some(rect));
+    assert_eq!(clipped.len(), 4);
+
+    let inside = [
+        Point::new(1.0, 1.0),
+        Point::new(3.0, 1.0),
+        Point::new(2.0, 3.0),
+    ];
+    assert_eq!(polygon::signed_area(&polygon::clip_to_rect(&inside, rect)),
+        polygon::signed_area(&inside));
+
+    let outside = [
+        Point::new(5.0, 1.0),
+        Point::new(7.0, 1.0),
+        Point::new(6.0, 3.0),
+    ];
+    assert!(polygon::clip_to_rect(&outside, rect).is_empty());
+}
+
+#[test]
+fn polygon_clip_to_rect_right_edge() {
+    let rect = Rect { left: 0.0, top: 0.0, right: 4.0, bottom: 4.0 };
+
+    // The right edge is exclusive, so a polygon touching it from outside is
+    // clipped away entirely.
+    let touching = [
+        Point::new(4.0, 1.0),
+        Point::new(6.0, 1.0),
+        Point::new(5.0, 3.0),
+    ];
+    assert!(polygon::clip_to_rect(&touching, rect).is_empty());
+
+    // A polygon inside the rect with an edge on its right edge keeps its area.
+    let square = [
+        Point::new(2.0, 1.0),
+        Point::new(4.0, 1.0),
+        Point::new(4.0, 3.0),
+        Point::new(2.0, 3.0),
+    ];
+    let clipped = polygon::clip_to_rect(&square, rect);
+    assert_eq!(polygon::signed_area(&clipped), polygon::signed_area(&square));
+    assert_eq!(polygon::bounds(&clipped), polygon::bounds(&square));
+}
+
+/// Returns the total area of the given contours.
+fn region_area(contours: &[Vec<Point>]) -> f32 {
+    contours.iter().map(|c| polygon::signed_area(c)).sum()
+}
+
+/// Returns `true` if the given contours contain the given point under the
+/// nonzero fill rule.
+fn region_contains(contours: &[Vec<Point>], pt: Point) -> bool {
+    contours.iter().map(|c| polygon::winding_number(c, pt)).sum::<i32>() != 0
+}
+
+#[test]
+fn polygon_boolean_overlapping() {
+    let a = [
+        Point::new(0.0, 0.0),
+        Point::new(4.0, 0.0),
+        Point::new(4.0, 4.0),
+        Point::new(0.0, 4.0),
+    ];
+    // Ordered counterclockwise.
+    let b = [
+        Point::new(2.0, 2.0),
+        Point::new(2.0, 6.0),
+        Point::new(6.0, 6.0),
+        Point::new(6.0, 2.0),
+    ];
+    let (only_a, both, only_b) =
+        (Point::new(1.0, 1.0), Point::new(3.0, 3.0), Point::new(5.0, 5.0));
+
+    let union = polygon::boolean(&a, &b, BooleanOp::Union);
+    assert_eq!(union.len(), 1);
+    assert_eq!(union[0].len(), 8);
+    assert_eq!(region_area(&union), 28.0);
+    assert!(region_contains(&union, only_a));
+    assert!(region_contains(&union, both));
+    assert!(region_contains(&union, only_b));
+
+    let intersection = polygon::boolean(&a, &b, BooleanOp::Intersection);
+    assert_eq!(intersection.len(), 1);
+    assert_eq!(polygon::bounds(&intersection[0]),
+        Some(Rect { left: 2.0, top: 2.0, right: 4.0, bottom: 4.0 }));
+    assert_eq!(region_area(&intersection), 4.0);
+
+    let difference = polygon::boolean(&a, &b, BooleanOp::Difference);
+    assert_eq!(region_area(&difference), 12.0);
+    assert!(region_contains(&difference, only_a));
+    assert!(!region_contains(&difference, both));
+    assert!(!region_contains(&difference, only_b));
+
+    let xor = polygon::boolean(&a, &b, BooleanOp::Xor);
+    assert_eq!(region_area(&xor), 24.0);
+    assert!(region_contains(&xor, only_a));
+    assert!(!region_contains(&xor, both));
+    assert!(region_contains(&xor, only_b));
+}
+
+#[test]
+fn polygon_boolean_hole() {
+    let outer = [
+        Point::new(0.0, 0.0),
+        Point::new(9.0, 0.0),
+        Point::new(9.0, 9.0),
+        Point::new(0.0, 9.0),
+    ];
+    let inner = [
+        Point::new(3.0, 3.0),
+        Point::new(6.0, 3.0),
+        Point::new(6.0, 6.0),
+        Point::new(3.0, 6.0),
+    ];
+
+    let difference = polygon::boolean(&outer, &inner, BooleanOp::Difference);
+    assert_eq!(difference.len(), 2);
+    assert_eq!(region_area(&difference), 72.0);
+    assert!(region_contains(&difference, Point::new(1.0, 1.0)));
+    assert!(!region_contains(&difference, Point::new(4.0, 4.0)));
+
+    let union = polygon::boolean(&outer, &inner, BooleanOp::Union);
+    assert_eq!(union, vec![outer.to_vec()]);
+
+    let intersection =
+        polygon::boolean(&outer, &inner, BooleanOp::Intersection);
+    assert_eq!(intersection, vec![inner.to_vec()]);
+
+    assert!(polygon::boolean(&inner, &outer, BooleanOp::Difference).is_empty());
+}
+
+#[test]
+fn polygon_boolean_shared_edge() {
+    let left = [
+        Point::new(0.0, 0.0),
+        Point::new(2.0, 0.0),
+        Point::new(2.0, 2.0),
+        Point::new(0.0, 2.0),
+    ];
+    let right = [
+        Point::new(2.0, 0.0),
+        Point::new(4.0, 0.0),
+        Point::new(4.0, 2.0),
+        Point::new(2.0, 2.0),
+    ];
+
+    let union = polygon::boolean(&left, &right, BooleanOp::Union);
+    assert_eq!(union.len(), 1);
+    assert_eq!(union[0].len(), 4);
+    assert_eq!(region_area(&union), 8.0);
+
+    assert!(polygon::boolean(&left, &right, BooleanOp::Intersection)
+        .is_empty());
+    assert_eq!(polygon::boolean(&left, &right, BooleanOp::Difference),
+        vec![left.to_vec()]);
+    assert_eq!(region_area(
+        &polygon::boolean(&left, &right, BooleanOp::Xor)), 8.0);
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // intersect_segment_with_segment
 ////////////////////////////////////////////////////////////////////////////////
@@ -112,7 +570,7 @@ fn extend_segment_to_rect_vertical_interior() {
     let segment = [Point::new(10.0, 10.0), Point::new(10.0, 60.0)];
 
     assert_eq!(extend_segment_to_rect(segment, rect),
-         Some([Point::new(10.0, 0.0), Point::new(10.0, 100.0)]));
+         Some(Segment::new(Point::new(10.0, 0.0), Point::new(10.0, 100.0))));
 }
 
 #[test]
@@ -122,7 +580,7 @@ fn extend_segment_to_rect_vertical_overlap() {
     let segment = [Point::new(10.0, -10.0), Point::new(10.0, 60.0)];
 
     assert_eq!(extend_segment_to_rect(segment, rect),
-         Some([Point::new(10.0, 0.0), Point::new(10.0, 100.0)]));
+         Some(Segment::new(Point::new(10.0, 0.0), Point::new(10.0, 100.0))));
 }
 
 #[test]
@@ -132,7 +590,7 @@ fn extend_segment_to_rect_vertical_exterior() {
     let segment = [Point::new(10.0, -10.0), Point::new(10.0, -60.0)];
 
     assert_eq!(extend_segment_to_rect(segment, rect),
-         Some([Point::new(10.0, 0.0), Point::new(10.0, 100.0)]));
+         Some(Segment::new(Point::new(10.0, 0.0), Point::new(10.0, 100.0))));
 }
 
 #[test]
@@ -142,7 +600,7 @@ fn extend_segment_to_rect_vertical_degenerate_edge() {
     let segment = [Point::new(0.0, -10.0), Point::new(0.0, -60.0)];
 
     assert_eq!(extend_segment_to_rect(segment, rect),
-         Some([Point::new(0.0, 0.0), Point::new(0.0, 100.0)]));
+         Some(Segment::new(Point::new(0.0, 0.0), Point::new(0.0, 100.0))));
 }
 
 #[test]
@@ -152,7 +610,7 @@ fn extend_segment_to_rect_horizontal_interior() {
     let segment = [Point::new(10.0, 10.0), Point::new(60.0, 10.0)];
 
     assert_eq!(extend_segment_to_rect(segment, rect),
-         Some([Point::new(0.0, 10.0), Point::new(100.0, 10.0)]));
+         Some(Segment::new(Point::new(0.0, 10.0), Point::new(100.0, 10.0))));
 }
 
 #[test]
@@ -162,7 +620,7 @@ fn extend_segment_to_rect_horizontal_overlap() {
     let segment = [Point::new(-10.0, 10.0), Point::new(60.0, 10.0)];
 
     assert_eq!(extend_segment_to_rect(segment, rect),
-         Some([Point::new(0.0, 10.0), Point::new(100.0, 10.0)]));
+         Some(Segment::new(Point::new(0.0, 10.0), Point::new(100.0, 10.0))));
 }
 
 #[test]
@@ -172,7 +630,7 @@ fn extend_segment_to_rect_horizontal_exterior() {
     let segment = [Point::new(-10.0, 10.0), Point::new(-60.0, 10.0)];
 
     assert_eq!(extend_segment_to_rect(segment, rect),
-         Some([Point::new(0.0, 10.0), Point::new(100.0, 10.0)]));
+         Some(Segment::new(Point::new(0.0, 10.0), Point::new(100.0, 10.0))));
 }
 
 #[test]
@@ -182,7 +640,7 @@ fn extend_segment_to_rect_horizontal_degenerate_edge() {
     let segment = [Point::new(-10.0, 0.0), Point::new(-60.0, 0.0)];
 
     assert_eq!(extend_segment_to_rect(segment, rect),
-         Some([Point::new(0.0, 0.0), Point::new(100.0, 0.0)]));
+         Some(Segment::new(Point::new(0.0, 0.0), Point::new(100.0, 0.0))));
 }
 
 
@@ -195,7 +653,7 @@ fn clip_segment_to_rect_interior() {
 
     assert_eq!(clip_segment_to_rect(
         [Point::new(10.0, 10.0), Point::new(10.0, 20.0)], rect),
-        Some([Point::new(10.0, 10.0), Point::new(10.0, 20.0)]));
+        Some(Segment::new(Point::new(10.0, 10.0), Point::new(10.0, 20.0))));
 }
 #[test]
 fn clip_segment_to_rect_exterior() {
@@ -203,7 +661,9 @@ fn clip_segment_to_rect_exterior() {
 
     assert_eq!(clip_segment_to_rect(
         [Point::new(-10.0, 10.0), Point::new(110.0, 20.0)], rect),
-        Some([Point::new(0.0, 10.833333), Point::new(100.0, 19.166668)]));
+        Some(Segment::new(
+            Point::new(0.0, 10.833333),
+            Point::new(100.0, 19.166668))));
 }
 
 #[test]
@@ -212,7 +672,7 @@ fn clip_segment_to_rect_vertical() {
 
     assert_eq!(clip_segment_to_rect(
         [Point::new(10.0, -10.0), Point::new(10.0, 20.0)], rect),
-        Some([Point::new(10.0, 0.0), Point::new(10.0, 20.0)]));
+        Some(Segment::new(Point::new(10.0, 0.0), Point::new(10.0, 20.0))));
 }
 
 #[test]
@@ -221,7 +681,7 @@ fn clip_segment_to_rect_horizontal() {
 
     assert_eq!(clip_segment_to_rect(
         [Point::new(80.0, 10.0), Point::new(120.0, 10.0)], rect),
-        Some([Point::new(80.0, 10.0), Point::new(100.0, 10.0)]));
+        Some(Segment::new(Point::new(80.0, 10.0), Point::new(100.0, 10.0))));
 }
 
 #[test]
@@ -230,7 +690,7 @@ fn clip_segment_to_rect_vertical_degenerate_edge() {
 
     assert_eq!(clip_segment_to_rect(
         [Point::new(0.0, -10.0), Point::new(0.0, 20.0)], rect),
-        Some([Point::new(0.0, 0.0), Point::new(0.0, 20.0)]));
+        Some(Segment::new(Point::new(0.0, 0.0), Point::new(0.0, 20.0))));
 }
 
 #[test]
@@ -239,7 +699,7 @@ fn clip_segment_to_rect_horizontal_degenerate_edge() {
 
     assert_eq!(clip_segment_to_rect(
         [Point::new(10.0, 0.0), Point::new(20.0, 0.0)], rect),
-        Some([Point::new(10.0, 0.0), Point::new(20.0, 0.0)]));
+        Some(Segment::new(Point::new(10.0, 0.0), Point::new(20.0, 0.0))));
 }
 
 #[test]
@@ -248,7 +708,7 @@ fn clip_segment_to_rect_degenerate_edge_point() {
 
     assert_eq!(clip_segment_to_rect(
         [Point::new(-120.0, -10.0), Point::new(65.0, 0.0)], rect),
-        Some([Point::new(65.0, 0.0), Point::new(65.0, 0.0)]));
+        Some(Segment::new(Point::new(65.0, 0.0), Point::new(65.0, 0.0))));
 }
 
 
@@ -258,7 +718,7 @@ fn clip_segment_to_rect_degenerate_corner() {
 
     assert_eq!(clip_segment_to_rect(
         [Point::new(-120.0, -10.0), Point::new(0.0, 0.0)], rect),
-        Some([Point::new(0.0, 0.0), Point::new(0.0, 0.0)]));
+        Some(Segment::new(Point::new(0.0, 0.0), Point::new(0.0, 0.0))));
 }
 
 
@@ -268,7 +728,7 @@ fn clip_segment_to_rect_degenerate_corner_tangent() {
 
     assert_eq!(clip_segment_to_rect(
         [Point::new(200.0, 0.0), Point::new(0.0, 200.0)], rect),
-        Some([Point::new(100.0, 100.0), Point::new(100.0, 100.0)]));
+        Some(Segment::new(Point::new(100.0, 100.0), Point::new(100.0, 100.0))));
 }
 
 #[test]
@@ -277,7 +737,7 @@ fn clip_segment_to_rect_degenerate_corner_tangent_reverse() {
 
     assert_eq!(clip_segment_to_rect(
         [Point::new(0.0, 200.0), Point::new(200.0, 0.0)], rect),
-        Some([Point::new(100.0, 100.0), Point::new(100.0, 100.0)]));
+        Some(Segment::new(Point::new(100.0, 100.0), Point::new(100.0, 100.0))));
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -289,7 +749,7 @@ fn clip_line_to_rect_vertical() {
 
     assert_eq!(clip_line_to_rect(
         Point::new(10.0, -10.0), PI / 2.0, rect),
-        Some([Point::new(10.0, 0.0), Point::new(10.0, 100.0)]));
+        Some(Segment::new(Point::new(10.0, 0.0), Point::new(10.0, 100.0))));
 }
 
 #[test]
@@ -298,7 +758,7 @@ fn clip_line_to_rect_horizontal() {
 
     assert_eq!(clip_line_to_rect(
         Point::new(-10.0, 10.0), PI, rect),
-        Some([Point::new(0.0, 10.0), Point::new(100.0, 10.0)]));
+        Some(Segment::new(Point::new(0.0, 10.0), Point::new(100.0, 10.0))));
 }
 
 #[test]
@@ -307,7 +767,7 @@ fn clip_line_to_rect_diagonal() {
 
     assert_eq!(clip_line_to_rect(
         Point::new(-10.0, -10.0), PI / 4.0, rect),
-        Some([Point::new(0.0, 0.0), Point::new(100.0, 100.0)]));
+        Some(Segment::new(Point::new(0.0, 0.0), Point::new(100.0, 100.0))));
 }
 
 #[test]
@@ -324,7 +784,7 @@ fn clip_line_to_rect_vertical_degenerate_edge() {
 
     assert_eq!(clip_line_to_rect(
         Point::new(0.0, -10.0), PI / 2.0, rect),
-        Some([Point::new(0.0, 0.0), Point::new(0.0, 100.0)]));
+        Some(Segment::new(Point::new(0.0, 0.0), Point::new(0.0, 100.0))));
 }
 
 #[test]
@@ -333,5 +793,91 @@ fn clip_line_to_rect_horizontal_degenerate_edge() {
 
     assert_eq!(clip_line_to_rect(
         Point::new(-10.0, 0.0), 0.0, rect),
-        Some([Point::new(0.0, 0.0), Point::new(100.0, 0.0)]));
+        Some(Segment::new(Point::new(0.0, 0.0), Point::new(100.0, 0.0))));
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Path
+////////////////////////////////////////////////////////////////////////////////
+#[test]
+fn path_flattened_contours() {
+    let mut path = Path::new();
+    path.move_to(Point::new(0.0, 0.0));
+    path.line_to(Point::new(2.0, 0.0));
+    path.line_to(Point::new(2.0, 2.0));
+    path.close();
+    path.move_to(Point::new(5.0, 5.0));
+    path.line_to(Point::new(6.0, 5.0));
+
+    assert_eq!(path.flattened(0.1), vec![
+        vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 0.0),
+        ],
+        vec![Point::new(5.0, 5.0), Point::new(6.0, 5.0)],
+    ]);
+}
+
+#[test]
+fn path_flattened_quadratic() {
+    let mut path = Path::new();
+    path.move_to(Point::new(0.0, 0.0));
+    path.quad_to(Point::new(5.0, 10.0), Point::new(10.0, 0.0));
+
+    let contour = &path.flattened(0.1)[0];
+    assert_eq!(contour.first(), Some(&Point::new(0.0, 0.0)));
+    assert_eq!(contour.last(), Some(&Point::new(10.0, 0.0)));
+    assert!(contour.len() > 3);
+    assert!(contour.iter().all(|pt| pt.y >= 0.0 && pt.y <= 5.0));
+}
+
+#[test]
+fn path_cubic_to() {
+    let (start, c1, c2, end) = (
+        Point::new(0.0, 0.0),
+        Point::new(0.0, 40.0),
+        Point::new(40.0, 40.0),
+        Point::new(40.0, 0.0));
+    let mut path = Path::new();
+    path.move_to(start);
+    path.cubic_to(c1, c2, end);
+
+    // The cubic is approximated by quadratics ending at its end point.
+    assert!(path.commands()[1..].iter()
+        .all(|c| matches!(*c, PathCommand::QuadTo(..))));
+
+    // Points on the cubic lie near the flattened path.
+    let contour = &path.flattened(0.05)[0];
+    assert_eq!(contour.last(), Some(&end));
+    for i in 0..=100 {
+        let t = i as f32 / 100.0;
+        let u = 1.0 - t;
+        let pt = start * (u * u * u)
+            + c1 * (3.0 * u * u * t)
+            + c2 * (3.0 * u * t * t)
+            + end * (t * t * t);
+        let distance = contour.windows(2)
+            .map(|w| Segment::new(w[0], w[1]).distance_to(pt))
+            .fold(f32::INFINITY, f32::min);
+        assert!(distance < 0.2, "{:?} is {} from the path", pt, distance);
+    }
+}
+
+#[test]
+fn path_bounds_and_transform() {
+    let mut path = Path::new();
+    path.move_to(Point::new(1.0, 2.0));
+    path.quad_to(Point::new(3.0, -1.0), Point::new(4.0, 4.0));
+
+    assert_eq!(path.bounds(),
+        Some(Rect { left: 1.0, top: -1.0, right: 4.0, bottom: 4.0 }));
+    assert_eq!(path.translated(Point::new(1.0, 1.0)).commands(), &[
+        PathCommand::MoveTo(Point::new(2.0, 3.0)),
+        PathCommand::QuadTo(Point::new(4.0, 0.0), Point::new(5.0, 5.0)),
+    ]);
+    assert_eq!(Path::new().bounds(), None);
 }
@@ -10,9 +10,13 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 // Module declarations.
+mod brush;
+mod fill;
 mod geometry;
 mod point;
 mod line;
+mod stroke;
+mod text;
 
 // Local imports.
 use canvas::Canvas;
@@ -6,13 +6,17 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 ////////////////////////////////////////////////////////////////////////////////
-//! Tests for point drawing primitives.
+//! Tests for point drawing primitives and vector math.
 ////////////////////////////////////////////////////////////////////////////////
 
 // Local imports.
 use geometry::Point;
 use primitive::point;
 use test::TestCanvas;
+use utilities::close;
+
+// Standard library imports.
+use std::f32::consts::PI;
 
 
 
@@ -66,4 +70,98 @@ pub fn edge() {
         0x00, 0x00, 0x00, 0x00, 0x00,
         0x00, 0x00, 0x00, 0x00, 0x00,
     ])
-}
\ No newline at end of file
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// `Point` vector math tests
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+pub fn scalar_operators() {
+    let mut pt = Point::new(1.5, -2.0);
+
+    assert_eq!(pt * 2.0, Point::new(3.0, -4.0));
+    assert_eq!(2.0 * pt, Point::new(3.0, -4.0));
+    assert_eq!(pt / 2.0, Point::new(0.75, -1.0));
+
+    pt *= 4.0;
+    assert_eq!(pt, Point::new(6.0, -8.0));
+    pt /= 2.0;
+    assert_eq!(pt, Point::new(3.0, -4.0));
+}
+
+#[test]
+pub fn dot_and_cross() {
+    let a = Point::new(2.0, 3.0);
+    let b = Point::new(-1.0, 4.0);
+
+    assert_eq!(a.dot(b), 10.0);
+    assert_eq!(a.cross(b), 11.0);
+    assert_eq!(b.cross(a), -11.0);
+    assert_eq!(a.cross(a), 0.0);
+    assert_eq!(a.dot(a.perpendicular()), 0.0);
+}
+
+#[test]
+pub fn length_and_normalization() {
+    let pt = Point::new(3.0, -4.0);
+
+    assert_eq!(pt.length(), 5.0);
+    assert_eq!(pt.length_squared(), 25.0);
+    assert!(pt.normalized().nearly_equal(Point::new(0.6, -0.8)));
+    assert!(close(pt.normalized().length(), 1.0, 1e-6));
+    assert_eq!(Point::default().normalized(), Point::default());
+}
+
+#[test]
+pub fn rotation() {
+    let pt = Point::new(2.0, 1.0);
+
+    assert_eq!(pt.perpendicular(), Point::new(-1.0, 2.0));
+    assert!(pt.rotated(0.0).nearly_equal(pt));
+
+    let quarter = pt.rotated(PI / 2.0);
+    assert!(quarter.nearly_equal(Point::new(-1.0, 2.0)));
+
+    let half = pt.rotated(PI);
+    assert!(half.nearly_equal(Point::new(-2.0, -1.0)));
+    assert!(close(half.length(), pt.length(), 1e-6));
+}
+
+#[test]
+pub fn rotation_round_off() {
+    let pt = Point::new(0.1, 0.2);
+    let full = pt.rotated(2.0 * PI);
+
+    assert!(full != pt);
+    assert!(full.nearly_equal(pt));
+    assert!(pt.rotated(PI).rotated(PI).nearly_equal(pt));
+}
+
+#[test]
+pub fn lerp_and_distance() {
+    let a = Point::new(1.0, 1.0);
+    let b = Point::new(5.0, -2.0);
+
+    assert!(a.lerp(b, 0.0).nearly_equal(a));
+    assert!(a.lerp(b, 1.0).nearly_equal(b));
+    assert!(a.lerp(b, 0.25).nearly_equal(Point::new(2.0, 0.25)));
+    assert!(a.lerp(b, 2.0).nearly_equal(b));
+    assert_eq!(a.distance(b), 5.0);
+    assert_eq!(b.distance(a), 5.0);
+}
+
+#[test]
+pub fn approximate_equality() {
+    let pt = Point::new(0.1, 0.2);
+
+    assert!(pt.nearly_equal(Point::new(0.1, 0.2)));
+    assert!(!pt.nearly_equal(Point::new(0.1, 0.2001)));
+    assert!(Point::new(0.0, 1.0).nearly_equal(Point::new(-0.0, 1.0)));
+    assert!(Point::new(1000.0, 0.0)
+        .nearly_equal(Point::new(1000.0 + 1e-3, 0.0)));
+    assert!(!Point::new(1000.0, 0.0)
+        .nearly_equal(Point::new(1000.1, 0.0)));
+}
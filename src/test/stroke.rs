@@ -0,0 +1,118 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Tests for brush stroke primitives.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use brush::Brush;
+use brush::RoundBrush;
+use brush::StrokePoint;
+use brush::StrokeStyle;
+use geometry::Path;
+use geometry::Point;
+use primitive::stroke;
+use primitive::stroke_path;
+use test::TestCanvas;
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// `stroke` tests
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+pub fn stroke_spacing_carries_between_segments() {
+    let mut c = TestCanvas::square(5);
+
+    stroke(&mut c, &0xFF, StrokeStyle::new(2.0), vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 3.0, y: 0.0 },
+        Point { x: 3.0, y: 4.0 },
+    ]);
+
+    assert_eq!(c.buffer, [
+        0xFF, 0x00, 0xFF, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0xFF, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0xFF, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
+
+#[test]
+pub fn stroke_empty() {
+    let mut c = TestCanvas::square(5);
+
+    stroke(&mut c, &0xFF, StrokeStyle::default(), Vec::<Point>::new());
+
+    assert_eq!(c.buffer, [0x00; 25].to_vec())
+}
+
+#[test]
+pub fn stroke_pressure_size() {
+    let mut c = TestCanvas::square(5);
+
+    let style = StrokeStyle { size: (1.0, 2.0), .. StrokeStyle::new(1.0) };
+    stroke(&mut c, &RoundBrush::new(0xFF, 1.0), style, vec![
+        StrokePoint::new(Point { x: 2.0, y: 2.0 }, 1.0),
+    ]);
+
+    assert_eq!(c.buffer, [
+        0x00, 0x00, 0xFF, 0x00, 0x00,
+        0x00, 0xFF, 0xFF, 0xFF, 0x00,
+        0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+        0x00, 0xFF, 0xFF, 0xFF, 0x00,
+        0x00, 0x00, 0xFF, 0x00, 0x00,
+    ])
+}
+
+#[test]
+pub fn brush_stroke_default_style() {
+    let mut c = TestCanvas::square(5);
+
+    0xFF.stroke(&mut c, &[
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 1.0, y: 3.0 },
+    ]);
+
+    assert_eq!(c.buffer, [
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xFF, 0x00, 0x00, 0x00,
+        0x00, 0xFF, 0x00, 0x00, 0x00,
+        0x00, 0xFF, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// `stroke_path` tests
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+pub fn stroke_path_closed_contour() {
+    let mut c = TestCanvas::square(5);
+    let mut path = Path::new();
+    path.move_to(Point { x: 1.0, y: 1.0 });
+    path.line_to(Point { x: 3.0, y: 1.0 });
+    path.line_to(Point { x: 3.0, y: 3.0 });
+    path.line_to(Point { x: 1.0, y: 3.0 });
+    path.close();
+
+    stroke_path(&mut c, &0xFF, StrokeStyle::default(), &path);
+
+    assert_eq!(c.buffer, [
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xFF, 0xFF, 0xFF, 0x00,
+        0x00, 0xFF, 0x00, 0xFF, 0x00,
+        0x00, 0xFF, 0xFF, 0xFF, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
@@ -0,0 +1,1076 @@
+// Copyright 2018 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//! Tests for text primitive support types.
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use geometry::Path;
+use geometry::Point;
+use geometry::Position;
+use geometry::Rect;
+use geometry::Scale;
+use primitive::BitmapFont;
+use primitive::DecorationMetrics;
+use primitive::fill_path;
+use primitive::FontSet;
+use primitive::FontStyle;
+use primitive::GlyphCache;
+use primitive::HorizontalAnchor;
+use primitive::prepare_glyph;
+use primitive::prepare_rich_text;
+use primitive::prepare_text;
+use primitive::prepare_text_fitted;
+use primitive::prepare_text_layout;
+use primitive::SubpixelOrder;
+use primitive::text;
+use primitive::TextAlign;
+use primitive::TextAnchor;
+use primitive::TextDirection;
+use primitive::TextEffects;
+use primitive::TextLayout;
+use primitive::TextOptions;
+use primitive::TextPath;
+use primitive::TextSpan;
+use primitive::TextWrap;
+use primitive::TruncateMode;
+use primitive::Typeface;
+use primitive::VerticalAnchor;
+use primitive::VMetrics;
+use test::TestCanvas;
+
+// Standard library imports.
+use std::cell::Cell;
+use std::f32::consts::FRAC_PI_2;
+use std::ops::Range;
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// `TextPath` tests
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+pub fn text_path_polyline_length() {
+    let path = TextPath::new(vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 3.0, y: 4.0 },
+        Point { x: 3.0, y: 4.0 },
+        Point { x: 3.0, y: 10.0 },
+    ]);
+
+    assert_eq!(path.points().len(), 3);
+    assert_eq!(path.length(), 11.0);
+}
+
+#[test]
+pub fn text_path_at_distance() {
+    let path = TextPath::new(vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 4.0, y: 0.0 },
+        Point { x: 4.0, y: 4.0 },
+    ]);
+
+    assert_eq!(path.at(2.0), Some((Point { x: 2.0, y: 0.0 }, 0.0)));
+    assert_eq!(path.at(6.0), Some((Point { x: 4.0, y: 2.0 }, FRAC_PI_2)));
+    assert_eq!(path.at(-1.0), None);
+    assert_eq!(path.at(9.0), None);
+}
+
+#[test]
+pub fn text_path_empty() {
+    let path = TextPath::new(vec![Point { x: 1.0, y: 1.0 }]);
+
+    assert_eq!(path.length(), 0.0);
+    assert_eq!(path.at(0.0), None);
+}
+
+#[test]
+pub fn text_path_from_path() {
+    let mut path = Path::new();
+    path.move_to(Point { x: 0.0, y: 0.0 });
+    path.line_to(Point { x: 4.0, y: 0.0 });
+    path.move_to(Point { x: 4.0, y: 3.0 });
+    path.quad_to(Point { x: 6.0, y: 3.0 }, Point { x: 8.0, y: 3.0 });
+
+    // The contours are joined, and the straight curve is followed exactly.
+    assert_eq!(TextPath::from_path(&path).length(), 11.0);
+    assert_eq!(
+        TextPath::from_path(&path).at(9.0),
+        Some((Point { x: 6.0, y: 3.0 }, 0.0)));
+}
+
+#[test]
+pub fn text_path_cubic_endpoints() {
+    let start = Point { x: 0.0, y: 0.0 };
+    let end = Point { x: 10.0, y: 0.0 };
+    let path = TextPath::cubic(
+        start,
+        Point { x: 0.0, y: 5.0 },
+        Point { x: 10.0, y: 5.0 },
+        end);
+
+    assert_eq!(path.points().first(), Some(&start));
+    assert_eq!(path.points().last(), Some(&end));
+    assert!(path.length() > 10.0 && path.length() < 20.0);
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// `BitmapFont` tests
+////////////////////////////////////////////////////////////////////////////////
+
+/// Returns a 2x2 bitmap font with diagonal glyphs for 'a' and 'b'.
+fn diagonal_font() -> BitmapFont {
+    BitmapFont::from_sheet(&[
+            true,  false, false, true,
+            false, true,  true,  false,
+        ],
+        4, 2, 2, 2, "ab")
+}
+
+#[test]
+pub fn bitmap_font_from_sheet() {
+    let font = diagonal_font();
+    let scale = Scale::new(2.0, 2.0);
+
+    assert!(font.has_glyph('a'));
+    assert!(font.has_glyph('b'));
+    assert!(!font.has_glyph('c'));
+    assert_eq!(font.advance(font.glyph_id('a'), scale), 2.0);
+    assert_eq!(font.v_metrics(scale).ascent, 2.0);
+    assert_eq!(font.v_metrics(scale).descent, 0.0);
+}
+
+#[test]
+pub fn bitmap_font_draw() {
+    let mut c = TestCanvas::square(5);
+    let font = diagonal_font();
+
+    text(&mut c, &TextOptions::new(&font, 0xFF), &(),
+        FontStyle::new(Scale::new(2.0, 2.0), false),
+        Point { x: 0.0, y: 1.0 },
+        "acb");
+
+    // The missing 'c' advances without drawing anything.
+    assert_eq!(c.buffer, [
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0xFF, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xFF, 0x00, 0x00, 0xFF,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
+
+#[test]
+pub fn bitmap_font_draw_scaled() {
+    let mut c = TestCanvas::square(5);
+    let font = diagonal_font();
+
+    text(&mut c, &TextOptions::new(&font, 0xFF), &(),
+        FontStyle::new(Scale::new(4.0, 4.0), false),
+        Point { x: 0.0, y: 0.0 },
+        "a");
+
+    assert_eq!(c.buffer, [
+        0xFF, 0xFF, 0x00, 0x00, 0x00,
+        0xFF, 0xFF, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0xFF, 0xFF, 0x00,
+        0x00, 0x00, 0xFF, 0xFF, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Subpixel rendering tests
+////////////////////////////////////////////////////////////////////////////////
+
+/// Draws a single pixel wide glyph at the center of a 3x3 canvas with the given
+/// subpixel order, returning the channels of the middle row.
+fn draw_subpixel_row(order: SubpixelOrder) -> Vec<[u8; 4]> {
+    let mut c = TestCanvas::square(3);
+    let font = BitmapFont::from_sheet(&[true], 1, 1, 1, 1, "i");
+
+    prepare_text(&font, FontStyle::new(Scale::new(1.0, 1.0), false), "i")
+        .draw_subpixel(&mut c, &0xFFFFFF, &(),
+            Point { x: 1.0, y: 1.0 },
+            TextAnchor::default(),
+            order);
+
+    c.buffer[3..6].iter().map(|p| p.to_bytes()).collect()
+}
+
+#[test]
+pub fn subpixel_rgb_fringes() {
+    let row = draw_subpixel_row(SubpixelOrder::Rgb);
+
+    // The coverage spreads into the nearest subpixels of each neighbor.
+    assert_eq!(row[0][0], 0);
+    assert!(row[0][2] > row[0][1] && row[0][1] > 0);
+    assert!(row[1][1] > row[1][0] && row[1][0] == row[1][2]);
+    assert!(row[2][0] > row[2][1] && row[2][1] > 0);
+    assert_eq!(row[2][2], 0);
+}
+
+#[test]
+pub fn subpixel_bgr_mirrors_rgb() {
+    let rgb = draw_subpixel_row(SubpixelOrder::Rgb);
+    let bgr = draw_subpixel_row(SubpixelOrder::Bgr);
+
+    for (a, b) in rgb.iter().zip(bgr.iter()) {
+        assert_eq!((a[0], a[1], a[2]), (b[2], b[1], b[0]));
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Text direction tests
+////////////////////////////////////////////////////////////////////////////////
+
+/// Returns a font of single opaque pixel glyphs for 'a', 'b', and 'c'.
+fn pixel_font() -> BitmapFont {
+    BitmapFont::from_sheet(&[true, true, true], 3, 1, 1, 1, "abc")
+}
+
+#[test]
+pub fn text_direction_right_to_left() {
+    let font = pixel_font();
+    let text = prepare_text_layout(&font,
+        FontStyle::new(Scale::new(1.0, 1.0), false),
+        "ab\nc",
+        TextLayout {
+            direction: TextDirection::RightToLeft,
+            .. Default::default()
+        });
+
+    // Characters advance leftward, and lines are aligned to the right edge.
+    assert_eq!(text.glyph_rects(), vec![
+        (0, Rect { left: 1.0, top: 0.0, right: 2.0, bottom: 1.0 }),
+        (1, Rect { left: 0.0, top: 0.0, right: 1.0, bottom: 1.0 }),
+        (3, Rect { left: 1.0, top: 1.0, right: 2.0, bottom: 2.0 }),
+    ]);
+    assert_eq!(text.caret(0)[0].x, 2.0);
+    assert_eq!(text.caret(2)[0].x, 0.0);
+    assert_eq!(text.index_at(Point { x: 1.8, y: 0.5 }), 0);
+    assert_eq!(text.index_at(Point { x: 0.2, y: 0.5 }), 2);
+}
+
+#[test]
+pub fn text_direction_top_to_bottom() {
+    let font = pixel_font();
+    let text = prepare_text_layout(&font,
+        FontStyle::new(Scale::new(1.0, 1.0), false),
+        "ab\nc",
+        TextLayout {
+            direction: TextDirection::TopToBottom,
+            .. Default::default()
+        });
+
+    // Characters advance downward, and columns advance leftward.
+    assert_eq!(text.glyph_rects(), vec![
+        (0, Rect { left: 1.0, top: 0.0, right: 2.0, bottom: 1.0 }),
+        (1, Rect { left: 1.0, top: 1.0, right: 2.0, bottom: 2.0 }),
+        (3, Rect { left: 0.0, top: 0.0, right: 1.0, bottom: 1.0 }),
+    ]);
+    assert_eq!((text.width(), text.height()), (2.0, 2.0));
+    assert_eq!(text.caret(1), [
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 2.0, y: 1.0 },
+    ]);
+    assert_eq!(text.index_at(Point { x: 0.5, y: 0.2 }), 3);
+    assert_eq!(text.index_at(Point { x: 1.5, y: 1.7 }), 2);
+}
+
+#[test]
+pub fn text_direction_top_to_bottom_draw() {
+    let mut c = TestCanvas::square(3);
+    let font = pixel_font();
+
+    prepare_text_layout(&font,
+        FontStyle::new(Scale::new(1.0, 1.0), false),
+        "ab\nc",
+        TextLayout {
+            direction: TextDirection::TopToBottom,
+            .. Default::default()
+        })
+        .draw(&mut c, &0xFF, &(), Point { x: 0.0, y: 0.0 },
+            TextAnchor::default());
+
+    assert_eq!(c.buffer, [
+        0xFF, 0xFF, 0x00,
+        0x00, 0xFF, 0x00,
+        0x00, 0x00, 0x00,
+    ])
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Text effect tests
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+pub fn text_effects_outline() {
+    let mut c = TestCanvas::square(5);
+    let font = pixel_font();
+
+    prepare_text(&font, FontStyle::new(Scale::new(1.0, 1.0), false), "a")
+        .draw_with_effects(&mut c, &0xFF, &(),
+            Point { x: 2.0, y: 2.0 },
+            TextAnchor::default(),
+            &TextEffects::new().with_outline(1.0, 0x11));
+
+    // Diagonal neighbors lie beyond the outline width and are partly covered.
+    assert_eq!(c.buffer, [
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x09, 0x11, 0x09, 0x00,
+        0x00, 0x11, 0xFF, 0x11, 0x00,
+        0x00, 0x09, 0x11, 0x09, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
+
+#[test]
+pub fn text_effects_shadow() {
+    let mut c = TestCanvas::square(5);
+    let font = pixel_font();
+
+    prepare_text(&font, FontStyle::new(Scale::new(1.0, 1.0), false), "a")
+        .draw_with_effects(&mut c, &0xFF, &(),
+            Point { x: 1.0, y: 1.0 },
+            TextAnchor::default(),
+            &TextEffects::new()
+                .with_shadow(Point { x: 2.0, y: 1.0 }, 0.0, 0x22));
+
+    assert_eq!(c.buffer, [
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xFF, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x22, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ])
+}
+
+#[test]
+pub fn text_effects_blurred_shadow() {
+    let mut c = TestCanvas::square(7);
+    let font = pixel_font();
+
+    prepare_text(&font, FontStyle::new(Scale::new(1.0, 1.0), false), "a")
+        .draw_with_effects(&mut c, &0xFF, &(),
+            Point { x: 3.0, y: 3.0 },
+            TextAnchor::default(),
+            &TextEffects::new()
+                .with_shadow(Point { x: 0.0, y: 0.0 }, 2.0, 0xFF));
+
+    // The blurred shadow spreads evenly around the glyph and fades outward.
+    let at = |x: usize, y: usize| c.buffer[y * 7 + x];
+    assert_eq!(at(3, 3), 0xFF);
+    assert!(at(2, 3) > at(1, 3) && at(1, 3) > 0);
+    assert_eq!(at(2, 3), at(4, 3));
+    assert_eq!(at(3, 2), at(3, 4));
+    assert_eq!(at(0, 0), 0);
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Text layout tests
+////////////////////////////////////////////////////////////////////////////////
+
+/// Lays out the given text with the pixel font and the given layout, returning
+/// the source character range of each line.
+fn layout_lines(text: &str, layout: TextLayout) -> Vec<Range<usize>> {
+    let font = pixel_font();
+    prepare_text_layout(&font,
+            FontStyle::new(Scale::new(1.0, 1.0), false),
+            text,
+            layout)
+        .lines()
+        .iter()
+        .map(|line| line.chars.clone())
+        .collect()
+}
+
+#[test]
+pub fn text_layout_breaks_at_wrap_width() {
+    // A line exactly as wide as the wrap width is not broken, and the space
+    // before the next word hangs past the end of the line.
+    assert_eq!(layout_lines("abc ab", TextLayout::new(3.0)), vec![0..4, 4..6]);
+    assert_eq!(layout_lines("ab ca", TextLayout::new(3.0)), vec![0..3, 3..5]);
+    assert_eq!(
+        layout_lines("abcab", TextLayout {
+            wrap: TextWrap::Character,
+            .. TextLayout::new(2.0)
+        }),
+        vec![0..2, 2..4, 4..5]);
+}
+
+#[test]
+pub fn text_layout_alignment_offsets() {
+    let font = pixel_font();
+    let left_of_short_line = |align| {
+        let text = prepare_text_layout(&font,
+            FontStyle::new(Scale::new(1.0, 1.0), false),
+            "abc\na",
+            TextLayout { align, .. Default::default() });
+        (text.lines()[1].left, text.glyph_rects()[3].1.left)
+    };
+
+    assert_eq!(left_of_short_line(TextAlign::Left), (0.0, 0.0));
+    assert_eq!(left_of_short_line(TextAlign::Center), (1.0, 1.0));
+    assert_eq!(left_of_short_line(TextAlign::Right), (2.0, 2.0));
+}
+
+#[test]
+pub fn text_layout_justified_width() {
+    let font = pixel_font();
+    let text = prepare_text_layout(&font,
+        FontStyle::new(Scale::new(1.0, 1.0), false),
+        "ab c abc",
+        TextLayout { align: TextAlign::Justify, .. TextLayout::new(5.0) });
+
+    // The first line is widened to the wrap width at its inner space, while
+    // the last line of the paragraph keeps its natural width.
+    assert_eq!(text.lines()[0].width, 5.0);
+    assert_eq!(text.lines()[1].width, 3.0);
+    assert_eq!(text.glyph_rects()[3],
+        (3, Rect { left: 4.0, top: 0.0, right: 5.0, bottom: 1.0 }));
+}
+
+#[test]
+pub fn text_layout_long_word() {
+    // A word wider than the wrap width is broken between characters, and a
+    // wrap width narrower than any glyph places one glyph on each line.
+    assert_eq!(layout_lines("abcab", TextLayout::new(2.0)),
+        vec![0..2, 2..4, 4..5]);
+    assert_eq!(layout_lines("abc", TextLayout::new(0.0)),
+        vec![0..1, 1..2, 2..3]);
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Text query tests
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+pub fn text_caret_index_round_trip() {
+    let font = pixel_font();
+    let text = prepare_text(&font,
+        FontStyle::new(Scale::new(1.0, 1.0), false),
+        "ab\nc");
+
+    // The end of the first line and the start of the second are distinct
+    // carets.
+    assert_eq!(text.caret(2)[0], Point { x: 2.0, y: 0.0 });
+    assert_eq!(text.caret(3)[0], Point { x: 0.0, y: 1.0 });
+    for index in 0..text.char_count() + 1 {
+        let [top, bottom] = text.caret(index);
+        assert_eq!(text.index_at((top + bottom) / 2.0), index);
+    }
+}
+
+#[test]
+pub fn text_index_at_outside_glyphs() {
+    let font = pixel_font();
+    let text = prepare_text_layout(&font,
+        FontStyle::new(Scale::new(1.0, 1.0), false),
+        "ab\nc",
+        TextLayout { line_spacing: 2.0, .. Default::default() });
+
+    // Before the first glyph and past the end of a line.
+    assert_eq!(text.index_at(Point { x: -5.0, y: 0.5 }), 0);
+    assert_eq!(text.index_at(Point { x: 10.0, y: 0.5 }), 2);
+    // Between lines, points belong to the line below.
+    assert_eq!(text.index_at(Point { x: 0.2, y: 1.5 }), 3);
+    assert_eq!(text.index_at(Point { x: 10.0, y: 1.5 }), 4);
+    // Above and below the text.
+    assert_eq!(text.index_at(Point { x: 1.8, y: -5.0 }), 2);
+    assert_eq!(text.index_at(Point { x: 0.2, y: 10.0 }), 3);
+}
+
+#[test]
+pub fn text_ink_rect_within_advance_rect() {
+    let mut font = BitmapFont::new(3, 3, 2);
+    font.insert('a', vec![
+        false, false, false,
+        false, true,  false,
+        false, false, false,
+    ]);
+    let text = prepare_text(&font,
+        FontStyle::new(Scale::new(3.0, 3.0), false),
+        "aa");
+
+    let ink = text.ink_rect().unwrap();
+    let advance = text.advance_rect();
+    assert_eq!(ink, Rect { left: 1.0, top: 1.0, right: 5.0, bottom: 2.0 });
+    assert_eq!(advance, Rect { left: 0.0, top: 0.0, right: 6.0, bottom: 3.0 });
+    assert!(ink.left >= advance.left && ink.right <= advance.right);
+    assert!(ink.top >= advance.top && ink.bottom <= advance.bottom);
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Text anchor tests
+////////////////////////////////////////////////////////////////////////////////
+
+/// Draws "aa" with a font of opaque glyphs one pixel wide and two pixels tall,
+/// with one row below the baseline, at the center of a 7x7 canvas with the
+/// given anchor. Returns the positions of the inked pixels.
+fn anchored_pixels(anchor: TextAnchor) -> Vec<(usize, usize)> {
+    let mut c = TestCanvas::square(7);
+    let font = BitmapFont::from_sheet(&[true, true], 1, 1, 2, 1, "a");
+
+    text(&mut c, &TextOptions::new(&font, 0xFF).with_anchor(anchor), &(),
+        FontStyle::new(Scale::new(2.0, 2.0), false),
+        Point { x: 3.0, y: 3.0 },
+        "aa");
+
+    (0..49)
+        .filter(|&i| c.buffer[i] != 0)
+        .map(|i| (i % 7, i / 7))
+        .collect()
+}
+
+#[test]
+pub fn text_anchor_top_left() {
+    assert_eq!(anchored_pixels(TextAnchor::top_left()),
+        vec![(3, 3), (4, 3), (3, 4), (4, 4)]);
+}
+
+#[test]
+pub fn text_anchor_center() {
+    assert_eq!(anchored_pixels(TextAnchor::center()),
+        vec![(2, 2), (3, 2), (2, 3), (3, 3)]);
+}
+
+#[test]
+pub fn text_anchor_baseline_right() {
+    // The baseline lies between the glyphs' rows, so the upper row is drawn
+    // just above the point and the descender row on it.
+    assert_eq!(
+        anchored_pixels(TextAnchor::new(
+            HorizontalAnchor::Right,
+            VerticalAnchor::Baseline)),
+        vec![(1, 2), (2, 2), (1, 3), (2, 3)]);
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Text decoration tests
+////////////////////////////////////////////////////////////////////////////////
+
+/// A bitmap font with decoration metrics.
+struct DecoratedFont {
+    font: BitmapFont,
+    metrics: DecorationMetrics,
+}
+
+impl Typeface for DecoratedFont {
+    fn glyph_id(&self, character: char) -> u32 {
+        self.font.glyph_id(character)
+    }
+
+    fn has_glyph(&self, character: char) -> bool {
+        self.font.has_glyph(character)
+    }
+
+    fn v_metrics(&self, scale: Scale) -> VMetrics {
+        self.font.v_metrics(scale)
+    }
+
+    fn advance(&self, glyph: u32, scale: Scale) -> f32 {
+        self.font.advance(glyph, scale)
+    }
+
+    fn bounds(&self, glyph: u32, scale: Scale) -> Option<Rect> {
+        self.font.bounds(glyph, scale)
+    }
+
+    fn scale_for_pixel_height(&self, height: f32) -> f32 {
+        self.font.scale_for_pixel_height(height)
+    }
+
+    fn decoration_metrics(&self) -> Option<DecorationMetrics> {
+        Some(self.metrics)
+    }
+
+    fn rasterize(
+        &self,
+        glyph: u32,
+        scale: Scale,
+        origin: Point,
+        coverage: &mut dyn FnMut(Position, f32))
+    {
+        self.font.rasterize(glyph, scale, origin, coverage);
+    }
+}
+
+/// Draws underlined text with the given font and style, returning the rows of
+/// the first column which are underlined.
+fn underline_rows<T: Typeface>(font: &T, font_style: FontStyle) -> Vec<usize> {
+    let mut c = TestCanvas::square(4);
+    text(&mut c, &TextOptions::new(font, 0x11), &0xFF, font_style,
+        Point { x: 0.0, y: 0.0 },
+        "ab");
+    (0..4).filter(|&y| c.buffer[y * 4] == 0xFF).collect()
+}
+
+#[test]
+pub fn decoration_metrics_from_typeface() {
+    let font_style = FontStyle::new(Scale::new(1.0, 1.0), true);
+    let metrics = DecorationMetrics {
+        underline_position: -2.0,
+        underline_thickness: 1.0,
+        strikeout_position: 0.0,
+        strikeout_thickness: 1.0,
+    };
+    let decorated = DecoratedFont { font: pixel_font(), metrics };
+
+    // Without metrics, the underline is placed just below the baseline.
+    assert_eq!(underline_rows(&pixel_font(), font_style), vec![1]);
+    assert_eq!(underline_rows(&decorated, font_style), vec![3]);
+}
+
+#[test]
+pub fn decoration_metrics_style_override() {
+    let font_style = FontStyle::new(Scale::new(1.0, 1.0), true);
+    let metrics = DecorationMetrics {
+        underline_position: -2.0,
+        underline_thickness: 1.0,
+        strikeout_position: 0.0,
+        strikeout_thickness: 1.0,
+    };
+    let decorated = DecoratedFont { font: pixel_font(), metrics };
+    let lowered = DecorationMetrics { underline_position: -3.0, .. metrics };
+
+    assert_eq!(
+        underline_rows(&pixel_font(),
+            font_style.with_decoration_metrics(metrics)),
+        vec![3]);
+    assert_eq!(
+        underline_rows(&decorated,
+            font_style.with_decoration_metrics(lowered)),
+        vec![]);
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// `GlyphCache` tests
+////////////////////////////////////////////////////////////////////////////////
+
+/// A bitmap font which counts the glyphs it rasterizes.
+struct CountingFont {
+    font: BitmapFont,
+    rasterized: Cell<usize>,
+}
+
+impl CountingFont {
+    /// Returns a `CountingFont` of single pixel glyphs for 'a', 'b', and 'c',
+    /// with an empty glyph for ' '.
+    fn new() -> Self {
+        CountingFont {
+            font: BitmapFont::from_sheet(
+                &[true, true, true, false], 4, 1, 1, 1, "abc "),
+            rasterized: Cell::new(0),
+        }
+    }
+
+    /// Draws the given text at the origin of the given canvas with the given
+    /// cache, returning the number of glyphs rasterized so far.
+    fn draw<'f>(
+        &'f self,
+        c: &mut TestCanvas,
+        cache: &mut GlyphCache<'f>,
+        text: &str)
+        -> usize
+    {
+        prepare_text(self, FontStyle::new(Scale::new(1.0, 1.0), false), text)
+            .draw_cached(c, &0xFF, &(),
+                Point { x: 0.0, y: 0.0 },
+                TextAnchor::default(),
+                cache);
+        self.rasterized.get()
+    }
+}
+
+impl Typeface for CountingFont {
+    fn glyph_id(&self, character: char) -> u32 {
+        self.font.glyph_id(character)
+    }
+
+    fn has_glyph(&self, character: char) -> bool {
+        self.font.has_glyph(character)
+    }
+
+    fn v_metrics(&self, scale: Scale) -> VMetrics {
+        self.font.v_metrics(scale)
+    }
+
+    fn advance(&self, glyph: u32, scale: Scale) -> f32 {
+        self.font.advance(glyph, scale)
+    }
+
+    fn bounds(&self, glyph: u32, scale: Scale) -> Option<Rect> {
+        self.font.bounds(glyph, scale)
+    }
+
+    fn scale_for_pixel_height(&self, height: f32) -> f32 {
+        self.font.scale_for_pixel_height(height)
+    }
+
+    fn rasterize(
+        &self,
+        glyph: u32,
+        scale: Scale,
+        origin: Point,
+        coverage: &mut dyn FnMut(Position, f32))
+    {
+        self.rasterized.set(self.rasterized.get() + 1);
+        self.font.rasterize(glyph, scale, origin, coverage);
+    }
+}
+
+#[test]
+pub fn glyph_cache_hits() {
+    let mut c = TestCanvas::square(5);
+    let mut cache = GlyphCache::new(16);
+    let font = CountingFont::new();
+
+    assert_eq!(font.draw(&mut c, &mut cache, "abab"), 2);
+    assert_eq!(font.draw(&mut c, &mut cache, "ba"), 2);
+    assert_eq!((cache.len(), cache.size()), (2, 2));
+}
+
+#[test]
+pub fn glyph_cache_empty_glyphs() {
+    let mut c = TestCanvas::square(5);
+    let mut cache = GlyphCache::new(16);
+    let font = CountingFont::new();
+
+    // Spaces are cached without coverage data, but still take up the minimum
+    // size of the cache's capacity.
+    assert_eq!(font.draw(&mut c, &mut cache, "a a"), 2);
+    assert_eq!(font.draw(&mut c, &mut cache, "  a"), 2);
+    assert_eq!((cache.len(), cache.size()), (2, 2));
+}
+
+#[test]
+pub fn glyph_cache_evicts_empty_glyphs() {
+    let mut c = TestCanvas::square(5);
+    let mut cache = GlyphCache::new(2);
+    let font = CountingFont::new();
+
+    assert_eq!(font.draw(&mut c, &mut cache, "ab"), 2);
+    // Caching ' ' evicts 'a', which was used least recently.
+    assert_eq!(font.draw(&mut c, &mut cache, " "), 3);
+    assert_eq!(font.draw(&mut c, &mut cache, "b "), 3);
+    assert_eq!(font.draw(&mut c, &mut cache, "a"), 4);
+    assert_eq!((cache.len(), cache.size()), (2, 2));
+}
+
+#[test]
+pub fn glyph_cache_evicts_least_recently_used() {
+    let mut c = TestCanvas::square(5);
+    let mut cache = GlyphCache::new(2);
+    let font = CountingFont::new();
+
+    assert_eq!(font.draw(&mut c, &mut cache, "a"), 1);
+    assert_eq!(font.draw(&mut c, &mut cache, "b"), 2);
+    assert_eq!(font.draw(&mut c, &mut cache, "a"), 2);
+    // Caching 'c' evicts 'b', which was used least recently.
+    assert_eq!(font.draw(&mut c, &mut cache, "c"), 3);
+    assert_eq!(font.draw(&mut c, &mut cache, "a"), 3);
+    assert_eq!(font.draw(&mut c, &mut cache, "b"), 4);
+    assert_eq!((cache.len(), cache.size()), (2, 2));
+}
+
+#[test]
+pub fn glyph_cache_matches_draw() {
+    let mut expected = TestCanvas::square(5);
+    let mut c = TestCanvas::square(5);
+    let mut cache = GlyphCache::new(64);
+    let font = diagonal_font();
+    let text = prepare_text(&font,
+        FontStyle::new(Scale::new(2.0, 2.0), false),
+        "ab\nba");
+
+    text.draw_clone(&mut expected, &0xFF, &(),
+        Point { x: 1.0, y: 0.0 },
+        TextAnchor::default());
+    // Draw twice to compare both newly cached and reused bitmaps.
+    for _ in 0..2 {
+        c.buffer.iter_mut().for_each(|p| *p = 0);
+        text.draw_cached(&mut c, &0xFF, &(),
+            Point { x: 1.0, y: 0.0 },
+            TextAnchor::default(),
+            &mut cache);
+        assert_eq!(c.buffer, expected.buffer);
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// `FontSet` tests
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+pub fn font_set_fallback() {
+    let primary = pixel_font();
+    // The fallback's 'a' is blank, so it is only visible if misused.
+    let fallback = BitmapFont::from_sheet(&[false, true], 2, 1, 1, 1, "ad");
+    let draw = |fonts: FontSet| {
+        let mut c = TestCanvas::square(3);
+        text(&mut c, &TextOptions::new(fonts, 0xFF), &(),
+            FontStyle::new(Scale::new(1.0, 1.0), false),
+            Point { x: 0.0, y: 0.0 },
+            "ad");
+        c.buffer[0..3].to_vec()
+    };
+
+    // The 'd' missing from the primary font is drawn from the fallback, while
+    // the 'a' is still drawn from the primary font.
+    assert_eq!(draw(FontSet::new(&primary)), vec![0xFF, 0x00, 0x00]);
+    assert_eq!(
+        draw(FontSet::new(&primary).with_fallback(&fallback)),
+        vec![0xFF, 0xFF, 0x00]);
+}
+
+#[test]
+pub fn font_set_fallback_metrics() {
+    let mut c = TestCanvas::square(3);
+    let primary = pixel_font();
+    // A fallback of glyphs two pixels tall, lying entirely above the baseline.
+    let tall = BitmapFont::from_sheet(&[true, true], 1, 1, 2, 2, "d");
+    let fonts = FontSet::new(&primary).with_fallback(&tall);
+    let font_style = FontStyle::new(Scale::new(1.0, 1.0), false);
+
+    // Lines are as tall as the tallest font supplying their glyphs.
+    text(&mut c, &TextOptions::new(fonts.clone(), 0xFF), &(), font_style,
+        Point { x: 0.0, y: 0.0 },
+        "ad");
+    assert_eq!(c.buffer, [
+        0x00, 0xFF, 0x00,
+        0xFF, 0xFF, 0x00,
+        0x00, 0x00, 0x00,
+    ]);
+
+    let baselines = |text: &str| prepare_text(fonts.clone(), font_style, text)
+        .lines()
+        .iter()
+        .map(|line| line.baseline)
+        .collect::<Vec<_>>();
+    assert_eq!(baselines("a\nd\na"), vec![1.0, 3.0, 4.0]);
+    assert_eq!(prepare_glyph(fonts.clone(), font_style, 'd').height(), 2.0);
+    assert_eq!(prepare_glyph(fonts.clone(), font_style, 'a').height(), 1.0);
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Rich text tests
+////////////////////////////////////////////////////////////////////////////////
+
+/// A bitmap font which adds a fixed kerning between every pair of glyphs.
+struct KernedFont {
+    font: BitmapFont,
+    kerning: f32,
+}
+
+impl Typeface for KernedFont {
+    fn glyph_id(&self, character: char) -> u32 {
+        self.font.glyph_id(character)
+    }
+
+    fn has_glyph(&self, character: char) -> bool {
+        self.font.has_glyph(character)
+    }
+
+    fn v_metrics(&self, scale: Scale) -> VMetrics {
+        self.font.v_metrics(scale)
+    }
+
+    fn advance(&self, glyph: u32, scale: Scale) -> f32 {
+        self.font.advance(glyph, scale)
+    }
+
+    fn kerning(&self, _scale: Scale, _first: u32, _second: u32) -> f32 {
+        self.kerning
+    }
+
+    fn bounds(&self, glyph: u32, scale: Scale) -> Option<Rect> {
+        self.font.bounds(glyph, scale)
+    }
+
+    fn scale_for_pixel_height(&self, height: f32) -> f32 {
+        self.font.scale_for_pixel_height(height)
+    }
+
+    fn rasterize(
+        &self,
+        glyph: u32,
+        scale: Scale,
+        origin: Point,
+        coverage: &mut dyn FnMut(Position, f32))
+    {
+        self.font.rasterize(glyph, scale, origin, coverage);
+    }
+}
+
+#[test]
+pub fn rich_text_span_styles() {
+    let mut c = TestCanvas::square(4);
+    let small = pixel_font();
+    // A font of glyphs two pixels tall, lying entirely above the baseline.
+    let tall = BitmapFont::from_sheet(&[true, true], 1, 1, 2, 2, "a");
+    let style = FontStyle::new(Scale::new(1.0, 1.0), false);
+
+    prepare_rich_text(vec![
+            TextSpan::new("ab", &small, style, 0x11, 0x33),
+            TextSpan::new("a", &tall, style.with_overline(true), 0x22, 0x33),
+        ], TextLayout::default())
+        .draw(&mut c, Point { x: 0.0, y: 0.0 }, TextAnchor::default());
+
+    // Both spans share the baseline of the taller span, and only the second
+    // span is overlined.
+    let at = |x: usize, y: usize| c.buffer[y * 4 + x];
+    assert_eq!((at(0, 0), at(1, 0), at(2, 0)), (0x00, 0x00, 0x33));
+    assert_eq!((at(0, 1), at(1, 1), at(2, 1)), (0x11, 0x11, 0x22));
+    assert_eq!((at(0, 2), at(1, 2), at(2, 2)), (0x00, 0x00, 0x00));
+}
+
+#[test]
+pub fn rich_text_kerning_across_spans() {
+    let font = KernedFont { font: pixel_font(), kerning: 1.0 };
+    let other = KernedFont { font: pixel_font(), kerning: 1.0 };
+    let style = FontStyle::new(Scale::new(1.0, 1.0), false);
+    let lefts = |spans: Vec<TextSpan<u32, u32>>| {
+        prepare_rich_text(spans, TextLayout::default())
+            .text()
+            .glyph_rects()
+            .iter()
+            .map(|&(_, rect)| rect.left)
+            .collect::<Vec<_>>()
+    };
+
+    // Spans sharing a face are kerned across their boundary, while spans with
+    // different faces or scales are not.
+    assert_eq!(
+        lefts(vec![
+            TextSpan::new("ab", &font, style, 0x11, 0x00),
+            TextSpan::new("c", &font, style, 0x22, 0x00),
+        ]),
+        vec![0.0, 2.0, 4.0]);
+    assert_eq!(
+        lefts(vec![
+            TextSpan::new("ab", &font, style, 0x11, 0x00),
+            TextSpan::new("c", &other, style, 0x22, 0x00),
+        ]),
+        vec![0.0, 2.0, 3.0]);
+    assert_eq!(
+        lefts(vec![
+            TextSpan::new("ab", &font, style, 0x11, 0x00),
+            TextSpan::new("c", &font,
+                FontStyle::new(Scale::new(2.0, 2.0), false), 0x22, 0x00),
+        ]),
+        vec![0.0, 2.0, 3.0]);
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Text truncation tests
+////////////////////////////////////////////////////////////////////////////////
+
+/// Returns a font of glyphs one pixel wide and two pixels tall, with the upper
+/// pixel set for 'a', 'b', and 'c', and the lower pixel set for '…'.
+fn truncation_font() -> BitmapFont {
+    BitmapFont::from_sheet(&[
+            true,  true,  true,  false,
+            false, false, false, true,
+        ],
+        4, 1, 2, 2, "abc…")
+}
+
+/// Fits the given text to the given width with the truncation font, returning
+/// the fitted text's character count and width, and its drawn pixels.
+fn fitted(text: &str, max_width: f32, mode: TruncateMode)
+    -> (usize, f32, Vec<u32>)
+{
+    let mut c = TestCanvas::square(6);
+    let font = truncation_font();
+    let fitted = prepare_text_fitted(&font,
+        FontStyle::new(Scale::new(2.0, 2.0), false),
+        text,
+        max_width,
+        mode);
+
+    fitted.draw_clone(&mut c, &0xFF, &(),
+        Point { x: 0.0, y: 0.0 },
+        TextAnchor::default());
+    (fitted.char_count(), fitted.width(), c.buffer[0..12].to_vec())
+}
+
+#[test]
+pub fn text_fitted_unchanged() {
+    assert_eq!(fitted("abc", 3.0, TruncateMode::End), (3, 3.0, vec![
+        0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ]));
+}
+
+#[test]
+pub fn text_fitted_ellipsis() {
+    // Four characters fit, so three are kept and followed by the ellipsis.
+    assert_eq!(fitted("abcabc", 4.0, TruncateMode::End), (4, 4.0, vec![
+        0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0xFF, 0x00, 0x00,
+    ]));
+    assert_eq!(fitted("abcabc", 4.5, TruncateMode::Start), (4, 4.0, vec![
+        0x00, 0xFF, 0xFF, 0xFF, 0x00, 0x00,
+        0xFF, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ]));
+    // Whitespace next to the ellipsis is removed.
+    assert_eq!(fitted("ab cab", 4.0, TruncateMode::End).0, 3);
+}
+
+#[test]
+pub fn text_fitted_narrower_than_ellipsis() {
+    assert_eq!(fitted("abc", 0.5, TruncateMode::End), (0, 0.0, vec![0; 12]));
+}
+
+#[test]
+pub fn text_fitted_multi_byte() {
+    // Characters missing from the font still advance, so the cut falls
+    // between multi-byte characters.
+    let modes = [TruncateMode::End, TruncateMode::Start, TruncateMode::Middle];
+    for &mode in &modes {
+        let (count, width, _) = fitted("aé€€éa", 4.0, mode);
+        assert_eq!((count, width), (4, 4.0));
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Text outline tests
+////////////////////////////////////////////////////////////////////////////////
+
+#[test]
+pub fn text_outline_fills_glyphs() {
+    let mut expected = TestCanvas::square(10);
+    let mut c = TestCanvas::square(10);
+    let font = diagonal_font();
+    let font_style = FontStyle::new(Scale::new(4.0, 4.0), false);
+    let pt = Point { x: 1.0, y: 1.0 };
+
+    text(&mut expected, &TextOptions::new(&font, 0xFF), &(), font_style, pt,
+        "ab");
+    let outline = prepare_text(&font, font_style, "ab").outline();
+    fill_path(&mut c, &0xFF, &outline.translated(pt));
+
+    assert!(expected.buffer.iter().any(|&p| p != 0));
+    assert_eq!(c.buffer, expected.buffer);
+}